@@ -22,6 +22,7 @@ use crate::multiaddr::{Multiaddr, Protocol};
 
 /// The endpoint roles associated with a peer-to-peer communication channel.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endpoint {
     /// The socket comes from a dialer.
     Dialer,
@@ -54,6 +55,7 @@ impl Endpoint {
 
 /// The endpoint roles associated with an established peer-to-peer connection.
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectedPoint {
     /// We dialed the node.
     Dialer {
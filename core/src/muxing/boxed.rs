@@ -211,3 +211,59 @@ impl AsyncWrite for SubstreamBox {
         self.0.as_mut().poll_close(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::muxing::StreamMuxerExt;
+    use crate::Multiaddr;
+    use futures::future::poll_fn;
+
+    /// A muxer that only ever reports a single address change.
+    struct AddressChangingMuxer(Option<Multiaddr>);
+
+    impl StreamMuxer for AddressChangingMuxer {
+        type Substream = SubstreamBox;
+        type Error = io::Error;
+
+        fn poll_inbound(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Result<Self::Substream, Self::Error>> {
+            Poll::Pending
+        }
+
+        fn poll_outbound(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Result<Self::Substream, Self::Error>> {
+            Poll::Pending
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Result<StreamMuxerEvent, Self::Error>> {
+            match self.get_mut().0.take() {
+                Some(addr) => Poll::Ready(Ok(StreamMuxerEvent::AddressChange(addr))),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    #[test]
+    fn address_change_is_forwarded() {
+        let addr = "/ip4/127.0.0.1/udp/1234/quic-v1"
+            .parse::<Multiaddr>()
+            .unwrap();
+        let mut muxer = StreamMuxerBox::new(AddressChangingMuxer(Some(addr.clone())));
+
+        let event = futures::executor::block_on(poll_fn(|cx| muxer.poll_unpin(cx))).unwrap();
+
+        assert!(matches!(event, StreamMuxerEvent::AddressChange(a) if a == addr));
+    }
+}
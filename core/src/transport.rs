@@ -176,6 +176,10 @@ pub trait Transport {
     fn address_translation(&self, listen: &Multiaddr, observed: &Multiaddr) -> Option<Multiaddr>;
 
     /// Boxes the transport, including custom transport errors.
+    ///
+    /// This erases the concrete type of the transport, leaving a [`Boxed`]
+    /// generic only over the output, e.g. `Boxed<(PeerId, StreamMuxerBox)>` after upgrading
+    /// and multiplexing. Errors are turned into [`std::io::Error`]s.
     fn boxed(self) -> boxed::Boxed<Self::Output>
     where
         Self: Sized + Send + Unpin + 'static,
@@ -247,6 +251,7 @@ pub trait Transport {
 
 /// The ID of a single listener.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListenerId(usize);
 
 impl ListenerId {
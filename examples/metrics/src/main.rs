@@ -22,13 +22,20 @@
 
 use env_logger::Env;
 use futures::{executor::block_on, StreamExt};
+use libp2p::bandwidth::BandwidthSinks;
 use libp2p::core::Multiaddr;
 use libp2p::metrics::{Metrics, Recorder};
 use libp2p::swarm::{NetworkBehaviour, SwarmEvent};
 use libp2p::{identify, identity, noise, ping, tcp, yamux};
 use log::info;
-use prometheus_client::registry::Registry;
+use prometheus_client::collector::Collector;
+use prometheus_client::metrics::counter::ConstCounter;
+use prometheus_client::metrics::family::ConstFamily;
+use prometheus_client::registry::{Descriptor, LocalMetric, Registry, Unit};
+use prometheus_client::MaybeOwned;
+use std::borrow::Cow;
 use std::error::Error;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
@@ -37,13 +44,15 @@ mod http_service;
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
 
-    let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+    let (builder, bandwidth_sinks) = libp2p::SwarmBuilder::with_new_identity()
         .with_async_std()
         .with_tcp(
             tcp::Config::default(),
             noise::Config::new,
             yamux::Config::default,
         )?
+        .with_bandwidth_logging();
+    let mut swarm = builder
         .with_behaviour(|key| Behaviour::new(key.public()))?
         .with_swarm_config(|cfg| cfg.with_idle_connection_timeout(Duration::from_secs(u64::MAX)))
         .build();
@@ -58,6 +67,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let mut metric_registry = Registry::default();
     let metrics = Metrics::new(&mut metric_registry);
+    metric_registry.register_collector(Box::new(Bandwidth(bandwidth_sinks)));
     thread::spawn(move || block_on(http_service::metrics_server(metric_registry)));
 
     block_on(async {
@@ -99,3 +109,40 @@ impl Behaviour {
         }
     }
 }
+
+/// Exposes the byte counters of the [`BandwidthSinks`] to Prometheus.
+#[derive(Debug)]
+struct Bandwidth(Arc<BandwidthSinks>);
+
+impl Collector for Bandwidth {
+    fn collect<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = (Cow<'a, Descriptor>, MaybeOwned<'a, Box<dyn LocalMetric>>)> + 'a>
+    {
+        let descriptor = Descriptor::new(
+            "libp2p_bandwidth",
+            "Bandwidth usage by direction",
+            Some(Unit::Bytes),
+            None,
+            vec![],
+        );
+        let bandwidth: Box<dyn LocalMetric> = Box::new(ConstFamily::new(
+            [
+                (
+                    [("direction", "inbound")],
+                    ConstCounter::new(self.0.total_inbound()),
+                ),
+                (
+                    [("direction", "outbound")],
+                    ConstCounter::new(self.0.total_outbound()),
+                ),
+            ]
+            .into_iter(),
+        ));
+
+        Box::new(std::iter::once((
+            Cow::Owned(descriptor),
+            MaybeOwned::Owned(bandwidth),
+        )))
+    }
+}
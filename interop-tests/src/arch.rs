@@ -8,6 +8,7 @@ pub(crate) use wasm::{build_swarm, init_logger, sleep, Instant, RedisClient};
 
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) mod native {
+    use std::net::IpAddr;
     use std::time::Duration;
 
     use anyhow::{bail, Context, Result};
@@ -42,6 +43,7 @@ pub(crate) mod native {
         muxer: Option<Muxer>,
         behaviour_constructor: impl FnOnce(&Keypair) -> B,
     ) -> Result<(Swarm<B>, String)> {
+        let ip = ip_multiaddr(ip)?;
         let (swarm, addr) = match (transport, sec_protocol, muxer) {
             (Transport::QuicV1, None, None) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -50,7 +52,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/udp/0/quic-v1"),
+                format!("{ip}/udp/0/quic-v1"),
             ),
             (Transport::Tcp, Some(SecProtocol::Tls), Some(Muxer::Mplex)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -63,7 +65,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0"),
+                format!("{ip}/tcp/0"),
             ),
             (Transport::Tcp, Some(SecProtocol::Tls), Some(Muxer::Yamux)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -76,7 +78,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0"),
+                format!("{ip}/tcp/0"),
             ),
             (Transport::Tcp, Some(SecProtocol::Noise), Some(Muxer::Mplex)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -89,7 +91,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0"),
+                format!("{ip}/tcp/0"),
             ),
             (Transport::Tcp, Some(SecProtocol::Noise), Some(Muxer::Yamux)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -102,7 +104,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0"),
+                format!("{ip}/tcp/0"),
             ),
             (Transport::Ws, Some(SecProtocol::Tls), Some(Muxer::Mplex)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -112,7 +114,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0/ws"),
+                format!("{ip}/tcp/0/ws"),
             ),
             (Transport::Ws, Some(SecProtocol::Tls), Some(Muxer::Yamux)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -122,7 +124,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0/ws"),
+                format!("{ip}/tcp/0/ws"),
             ),
             (Transport::Ws, Some(SecProtocol::Noise), Some(Muxer::Mplex)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -132,7 +134,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0/ws"),
+                format!("{ip}/tcp/0/ws"),
             ),
             (Transport::Ws, Some(SecProtocol::Noise), Some(Muxer::Yamux)) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -142,7 +144,7 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/tcp/0/ws"),
+                format!("{ip}/tcp/0/ws"),
             ),
             (Transport::WebRtcDirect, None, None) => (
                 libp2p::SwarmBuilder::with_new_identity()
@@ -156,13 +158,26 @@ pub(crate) mod native {
                     .with_behaviour(behaviour_constructor)?
                     .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(5)))
                     .build(),
-                format!("/ip4/{ip}/udp/0/webrtc-direct"),
+                format!("{ip}/udp/0/webrtc-direct"),
             ),
             (t, s, m) => bail!("Unsupported combination: {t:?} {s:?} {m:?}"),
         };
         Ok((swarm, addr))
     }
 
+    /// Turns the IP address to listen on into a multiaddr, e.g. `0.0.0.0` into `/ip4/0.0.0.0` and
+    /// `::` into `/ip6/::`.
+    fn ip_multiaddr(ip: &str) -> Result<String> {
+        let ip = ip
+            .parse::<IpAddr>()
+            .with_context(|| format!("invalid IP address {ip}"))?;
+
+        Ok(match ip {
+            IpAddr::V4(ip) => format!("/ip4/{ip}"),
+            IpAddr::V6(ip) => format!("/ip6/{ip}"),
+        })
+    }
+
     pub(crate) struct RedisClient(redis::Client);
 
     impl RedisClient {
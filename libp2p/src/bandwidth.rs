@@ -101,6 +101,7 @@ where
 }
 
 /// Allows obtaining the average bandwidth of the streams.
+#[derive(Debug)]
 pub struct BandwidthSinks {
     inbound: AtomicU64,
     outbound: AtomicU64,
@@ -13,6 +13,9 @@ pub struct BandwidthLoggingPhase<T, R> {
 impl<T: AuthenticatedMultiplexedTransport, Provider, R>
     SwarmBuilder<Provider, BandwidthLoggingPhase<T, R>>
 {
+    /// Instrument the transport to count the bytes sent and received over all its connections.
+    ///
+    /// See [`TransportExt::with_bandwidth_logging`] for details.
     pub fn with_bandwidth_logging(
         self,
     ) -> (
@@ -33,6 +36,7 @@ impl<T: AuthenticatedMultiplexedTransport, Provider, R>
         )
     }
 
+    /// Skip bandwidth logging, leaving the transport as is.
     pub fn without_bandwidth_logging(self) -> SwarmBuilder<Provider, BehaviourPhase<T, R>> {
         SwarmBuilder {
             phase: BehaviourPhase {
@@ -15,6 +15,11 @@ impl SwarmBuilder<NoProviderSpecified, ProviderPhase> {
         }
     }
 
+    /// Uses the Tokio runtime for the transports and the [`Swarm`](libp2p_swarm::Swarm) tasks.
+    ///
+    /// The [`Swarm`](libp2p_swarm::Swarm) has to be built and polled within the context of a
+    /// Tokio runtime, e.g. in a `#[tokio::main]` function or a future passed to
+    /// [`Runtime::block_on`](https://docs.rs/tokio/latest/tokio/runtime/struct.Runtime.html#method.block_on).
     #[cfg(all(not(target_arch = "wasm32"), feature = "tokio"))]
     pub fn with_tokio(self) -> SwarmBuilder<Tokio, TcpPhase> {
         SwarmBuilder {
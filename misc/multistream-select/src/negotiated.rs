@@ -192,6 +192,44 @@ impl<TInner> Negotiated<TInner> {
     pub fn complete(self) -> NegotiatedComplete<TInner> {
         NegotiatedComplete { inner: Some(self) }
     }
+
+    /// Returns the underlying I/O stream if protocol negotiation is complete,
+    /// or `self` unchanged otherwise.
+    ///
+    /// Await [`Negotiated::complete`] first to make sure negotiation has
+    /// completed. Reading from and writing to the returned stream bypasses
+    /// `Negotiated` entirely, which allows a protocol to apply its own framing
+    /// on top of the raw stream.
+    ///
+    /// # Example
+    ///
+    /// A protocol that inspects the leading bytes of the stream once negotiation
+    /// has completed:
+    ///
+    /// ```
+    /// # use futures::prelude::*;
+    /// # use multistream_select::{Negotiated, NegotiationError};
+    /// async fn read_preamble<R>(io: Negotiated<R>) -> Result<([u8; 4], R), NegotiationError>
+    /// where
+    ///     R: AsyncRead + AsyncWrite + Unpin,
+    /// {
+    ///     let negotiated = io.complete().await?;
+    ///     let mut raw = negotiated
+    ///         .into_inner()
+    ///         .unwrap_or_else(|_| unreachable!("negotiation to be complete"));
+    ///
+    ///     let mut preamble = [0u8; 4];
+    ///     raw.read_exact(&mut preamble).await?;
+    ///
+    ///     Ok((preamble, raw))
+    /// }
+    /// ```
+    pub fn into_inner(self) -> Result<TInner, Self> {
+        match self.state {
+            State::Completed { io } => Ok(io),
+            state => Err(Negotiated { state }),
+        }
+    }
 }
 
 /// The states of a `Negotiated` I/O stream.
@@ -280,6 +318,33 @@ where
     }
 }
 
+impl<TInner> AsyncBufRead for Negotiated<TInner>
+where
+    TInner: AsyncBufRead + AsyncWrite + Unpin,
+{
+    fn poll_fill_buf(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        // Drive protocol negotiation to completion, including flushing of any remaining data.
+        while !matches!(self.state, State::Completed { .. }) {
+            if let Err(err) = ready!(self.as_mut().poll(cx)) {
+                return Poll::Ready(Err(From::from(err)));
+            }
+        }
+
+        match self.project().state.project() {
+            StateProj::Completed { io } => io.poll_fill_buf(cx),
+            _ => unreachable!("protocol negotiation is complete"),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        match self.project().state.project() {
+            StateProj::Completed { io } => io.consume(amt),
+            // Nothing can have been read before protocol negotiation is complete.
+            _ => debug_assert_eq!(amt, 0, "consume called before poll_fill_buf"),
+        }
+    }
+}
+
 impl<TInner> AsyncWrite for Negotiated<TInner>
 where
     TInner: AsyncWrite + AsyncRead + Unpin,
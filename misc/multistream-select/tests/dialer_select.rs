@@ -69,6 +69,87 @@ fn select_proto_basic() {
     async_std::task::block_on(run(Version::V1Lazy));
 }
 
+#[test]
+fn negotiated_buffered_reads() {
+    async fn run(version: Version) {
+        let (client_connection, server_connection) = futures_ringbuf::Endpoint::pair(100, 100);
+
+        let server = async_std::task::spawn(async move {
+            let server_connection = futures::io::BufReader::new(server_connection);
+            let (_, mut io) = listener_select_proto(server_connection, vec!["/proto1"])
+                .await
+                .unwrap();
+
+            let mut line = String::new();
+            io.read_line(&mut line).await.unwrap();
+            assert_eq!(line, "ping\n");
+            line.clear();
+            io.read_line(&mut line).await.unwrap();
+            assert_eq!(line, "ping again\n");
+        });
+
+        let client = async_std::task::spawn(async move {
+            let client_connection = futures::io::BufReader::new(client_connection);
+            let (_, mut io) = dialer_select_proto(client_connection, vec!["/proto1"], version)
+                .await
+                .unwrap();
+
+            io.write_all(b"ping\nping again\n").await.unwrap();
+            io.flush().await.unwrap();
+
+            // Wait for the server to close the stream.
+            let mut out = Vec::new();
+            io.read_to_end(&mut out).await.unwrap();
+            assert!(out.is_empty());
+        });
+
+        server.await;
+        client.await;
+    }
+
+    async_std::task::block_on(run(Version::V1));
+    async_std::task::block_on(run(Version::V1Lazy));
+}
+
+#[test]
+fn negotiated_into_inner() {
+    async fn run(version: Version) {
+        let (client_connection, server_connection) = futures_ringbuf::Endpoint::pair(100, 100);
+
+        let server = async_std::task::spawn(async move {
+            let (_, io) = listener_select_proto(server_connection, vec!["/proto1"])
+                .await
+                .unwrap();
+            let mut io = io.into_inner().unwrap();
+
+            let mut preamble = [0u8; 4];
+            io.read_exact(&mut preamble).await.unwrap();
+            assert_eq!(&preamble, b"ping");
+        });
+
+        let client = async_std::task::spawn(async move {
+            let (_, io) = dialer_select_proto(client_connection, vec!["/proto1"], version)
+                .await
+                .unwrap();
+            let mut io = io
+                .complete()
+                .await
+                .unwrap()
+                .into_inner()
+                .unwrap_or_else(|_| panic!("Expect negotiation to be complete"));
+
+            io.write_all(b"ping").await.unwrap();
+            io.flush().await.unwrap();
+        });
+
+        server.await;
+        client.await;
+    }
+
+    async_std::task::block_on(run(Version::V1));
+    async_std::task::block_on(run(Version::V1Lazy));
+}
+
 /// Tests the expected behaviour of failed negotiations.
 #[test]
 fn negotiation_failed() {
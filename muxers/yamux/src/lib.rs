@@ -141,6 +141,15 @@ where
 #[derive(Debug)]
 pub struct Stream(yamux::Stream);
 
+impl Stream {
+    /// The yamux stream ID of this stream, e.g. to correlate log lines.
+    ///
+    /// Stream IDs are unique within a connection only.
+    pub fn id(&self) -> u32 {
+        self.0.id().val()
+    }
+}
+
 impl AsyncRead for Stream {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -201,6 +210,11 @@ where
 }
 
 /// The yamux configuration.
+///
+/// Yamux does not send keep-alive pings on its own, a connection whose remote silently went
+/// away is only detected once the underlying transport fails. To detect dead connections
+/// independently of the transport, use the ping protocol of `libp2p-ping` and close
+/// connections once pings fail.
 #[derive(Debug, Clone)]
 pub struct Config {
     inner: yamux::Config,
@@ -267,6 +281,10 @@ impl Config {
     }
 
     /// Sets the size (in bytes) of the receive window per substream.
+    ///
+    /// Yamux has no connection-level window. The number of bytes a remote can send without
+    /// us reading them is bounded by this window times [`Config::set_max_num_streams`], so
+    /// lower either of the two to cap the memory a single connection can occupy.
     pub fn set_receive_window_size(&mut self, num_bytes: u32) -> &mut Self {
         self.inner.set_receive_window(num_bytes);
         self
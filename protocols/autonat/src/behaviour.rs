@@ -57,6 +57,10 @@ pub struct Config {
 
     // Client Config
     /// Delay on init before starting the fist probe.
+    ///
+    /// Subsequent probes are scheduled relative to the first one. To keep many nodes that start at
+    /// the same time from probing the same servers simultaneously, randomize this delay per node,
+    /// e.g. `Duration::from_millis(rand::thread_rng().gen_range(10_000..20_000))`.
     pub boot_delay: Duration,
     /// Interval in which the NAT should be tested again if max confidence was reached in a status.
     pub refresh_interval: Duration,
@@ -18,6 +18,7 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use libp2p_core::{multiaddr::Protocol, Multiaddr};
 use libp2p_swarm::StreamProtocol;
 
 pub(crate) mod inbound;
@@ -25,3 +26,30 @@ pub(crate) mod outbound;
 pub const PROTOCOL_NAME: StreamProtocol = StreamProtocol::new("/libp2p/dcutr");
 
 const MAX_MESSAGE_SIZE_BYTES: usize = 4096;
+
+/// Whether the address is an IPv6 link-local address (`fe80::/10`).
+///
+/// These are only valid on the local link and are thus of no use for hole punching.
+fn is_link_local_ip6(addr: &Multiaddr) -> bool {
+    addr.iter()
+        .any(|p| matches!(p, Protocol::Ip6(ip) if ip.segments()[0] & 0xffc0 == 0xfe80))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_ip6_link_local_addresses_are_detected() {
+        for (addr, expected) in [
+            ("/ip6/fe80::1/udp/4001/quic-v1", true),
+            ("/ip6/febf::1/tcp/4001", true),
+            ("/ip6/::1/tcp/4001", false),
+            ("/ip6/2001:db8::1/tcp/4001", false),
+            ("/ip4/169.254.0.1/tcp/4001", false),
+        ] {
+            let addr = addr.parse::<Multiaddr>().unwrap();
+            assert_eq!(is_link_local_ip6(&addr), expected, "{addr}");
+        }
+    }
+}
@@ -100,6 +100,15 @@ impl upgrade::OutboundUpgrade<Stream> for Upgrade {
                             true
                         }
                     })
+                    // Filter out link-local addresses, they are not reachable from another network.
+                    .filter(|a| {
+                        if super::is_link_local_ip6(a) {
+                            log::debug!("Dropping link-local address {a}");
+                            false
+                        } else {
+                            true
+                        }
+                    })
                     .collect::<Vec<Multiaddr>>()
             };
 
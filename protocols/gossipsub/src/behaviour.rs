@@ -62,7 +62,7 @@ use crate::types::{
     ControlAction, FastMessageId, Message, MessageAcceptance, MessageId, PeerInfo, RawMessage,
     Subscription, SubscriptionAction,
 };
-use crate::types::{PeerConnections, PeerKind, Rpc};
+use crate::types::{HeartbeatStats, PeerConnections, PeerKind, Rpc, TopicMeshState};
 use crate::{rpc_proto::proto, TopicScoreParams};
 use crate::{PublishError, SubscriptionError, ValidationError};
 use instant::SystemTime;
@@ -295,6 +295,12 @@ pub struct Behaviour<D = IdentityTransform, F = AllowAllSubscriptionFilter> {
     /// clean up -- eg backoff clean up.
     heartbeat_ticks: u64,
 
+    /// When the last heartbeat ran.
+    last_heartbeat: Option<Instant>,
+
+    /// Summary of the last heartbeat.
+    last_heartbeat_stats: Option<HeartbeatStats>,
+
     /// We remember all peers we found through peer exchange, since those peers are not considered
     /// as safe as randomly discovered outbound peers. This behaviour diverges from the go
     /// implementation to avoid possible love bombing attacks in PX. When disconnecting peers will
@@ -459,12 +465,15 @@ where
                 config.heartbeat_interval(),
                 config.backoff_slack(),
             ),
-            mcache: MessageCache::new(config.history_gossip(), config.history_length()),
+            mcache: MessageCache::new(config.history_gossip(), config.history_length())
+                .with_topic_gossip(config.topic_history_gossip_overrides().clone()),
             heartbeat: Ticker::new_with_next(
                 config.heartbeat_interval(),
                 config.heartbeat_initial_delay(),
             ),
             heartbeat_ticks: 0,
+            last_heartbeat: None,
+            last_heartbeat_stats: None,
             px_peers: HashSet::new(),
             outbound_peers: HashSet::new(),
             peer_score: None,
@@ -495,6 +504,15 @@ where
         self.mesh.get(topic_hash).into_iter().flat_map(|x| x.iter())
     }
 
+    /// Lists all fanout peers for a certain topic hash, i.e. the peers we publish to on a topic
+    /// we are not subscribed to.
+    pub fn fanout_peers(&self, topic_hash: &TopicHash) -> impl Iterator<Item = &PeerId> {
+        self.fanout
+            .get(topic_hash)
+            .into_iter()
+            .flat_map(|x| x.iter())
+    }
+
     pub fn all_mesh_peers(&self) -> impl Iterator<Item = &PeerId> {
         let mut res = BTreeSet::new();
         for peers in self.mesh.values() {
@@ -503,6 +521,45 @@ where
         res.into_iter()
     }
 
+    /// Returns a snapshot of the local state of a topic, e.g. for exposing it on a health
+    /// endpoint.
+    ///
+    /// Returns `None` if we are neither subscribed to the topic nor have fanout peers for it.
+    pub fn topic_mesh_state(&self, topic_hash: &TopicHash) -> Option<TopicMeshState> {
+        let mesh = self.mesh.get(topic_hash);
+        let fanout = self.fanout.get(topic_hash);
+        if mesh.is_none() && fanout.is_none() {
+            return None;
+        }
+
+        Some(TopicMeshState {
+            topic: topic_hash.clone(),
+            mesh_peers: mesh.map_or(0, |peers| peers.len()),
+            fanout_peers: fanout.map_or(0, |peers| peers.len()),
+            mcache_size: self.mcache.topic_message_count(topic_hash),
+            subscribed: mesh.is_some(),
+            last_heartbeat: self.last_heartbeat,
+        })
+    }
+
+    /// Returns a snapshot of the local state of all topics we are subscribed to or have fanout
+    /// peers for.
+    ///
+    /// See [`Behaviour::topic_mesh_state`].
+    pub fn all_topic_states(&self) -> impl Iterator<Item = TopicMeshState> + '_ {
+        self.mesh
+            .keys()
+            .chain(self.fanout.keys().filter(|t| !self.mesh.contains_key(t)))
+            .filter_map(|topic_hash| self.topic_mesh_state(topic_hash))
+    }
+
+    /// Returns a summary of the last heartbeat, e.g. the number of GRAFTs and PRUNEs it sent.
+    ///
+    /// Returns `None` if no heartbeat has run yet.
+    pub fn last_heartbeat_stats(&self) -> Option<&HeartbeatStats> {
+        self.last_heartbeat_stats.as_ref()
+    }
+
     /// Lists all known peers and their associated subscribed topics.
     pub fn all_peers(&self) -> impl Iterator<Item = (&PeerId, Vec<&TopicHash>)> {
         self.peer_topics
@@ -656,11 +713,20 @@ where
 
         let topic_hash = raw_message.topic.clone();
 
+        // Large messages are announced via IHAVE and pulled by the recipients via IWANT.
+        let lazy = raw_message.data.len() > self.config.lazy_publish_threshold();
+
         // If we are not flood publishing forward the message to mesh peers.
         let mesh_peers_sent = !self.config.flood_publish()
+            && !lazy
             && self.forward_msg(&msg_id, raw_message.clone(), None, HashSet::new())?;
 
         let mut recipient_peers = HashSet::new();
+        if lazy && !self.config.flood_publish() {
+            if let Some(mesh_peers) = self.mesh.get(&topic_hash) {
+                recipient_peers.extend(mesh_peers.iter().cloned());
+            }
+        }
         if let Some(set) = self.topic_peers.get(&topic_hash) {
             if self.config.flood_publish() {
                 // Forward to all peers above score and all explicit peers
@@ -701,7 +767,7 @@ where
                         }
                     } else {
                         // We have no fanout peers, select mesh_n of them and add them to the fanout
-                        let mesh_n = self.config.mesh_n();
+                        let mesh_n = self.config.topic_mesh_params(&topic_hash).mesh_n;
                         let new_peers = get_random_peers(
                             &self.topic_peers,
                             &self.connected_peers,
@@ -750,6 +816,35 @@ where
         // Send to peers we know are subscribed to the topic.
         let msg_bytes = event.get_size();
         for peer_id in recipient_peers.iter() {
+            // Explicit and floodsub peers don't pull messages, send them the full message.
+            if lazy
+                && !self.explicit_peers.contains(peer_id)
+                && self
+                    .connected_peers
+                    .get(peer_id)
+                    .map_or(false, |c| c.kind != PeerKind::Floodsub)
+            {
+                // Announce the message with the next heartbeat, batched into a single IHAVE per
+                // topic, as peers ignore more than `max_ihave_messages` IHAVEs per heartbeat.
+                trace!("Queueing IHAVE for message to peer: {:?}", peer_id);
+                let controls = self.control_pool.entry(*peer_id).or_default();
+                let pending_ihave = controls.iter_mut().find_map(|control| match control {
+                    ControlAction::IHave {
+                        topic_hash: ihave_topic,
+                        message_ids,
+                    } if *ihave_topic == topic_hash => Some(message_ids),
+                    _ => None,
+                });
+                match pending_ihave {
+                    Some(message_ids) => message_ids.push(msg_id.clone()),
+                    None => controls.push(ControlAction::IHave {
+                        topic_hash: topic_hash.clone(),
+                        message_ids: vec![msg_id.clone()],
+                    }),
+                }
+                continue;
+            }
+
             trace!("Sending message to peer: {:?}", peer_id);
             self.send_message(*peer_id, event.clone())?;
 
@@ -948,6 +1043,7 @@ where
     /// Gossipsub JOIN(topic) - adds topic peers to mesh and sends them GRAFT messages.
     fn join(&mut self, topic_hash: &TopicHash) {
         debug!("Running JOIN for topic: {:?}", topic_hash);
+        let mesh_n = self.config.topic_mesh_params(topic_hash).mesh_n;
 
         // if we are already in the mesh, return
         if self.mesh.contains_key(topic_hash) {
@@ -978,7 +1074,7 @@ where
 
             // Add up to mesh_n of them them to the mesh
             // NOTE: These aren't randomly added, currently FIFO
-            let add_peers = std::cmp::min(peers.len(), self.config.mesh_n());
+            let add_peers = std::cmp::min(peers.len(), mesh_n);
             debug!(
                 "JOIN: Adding {:?} peers from the fanout for topic: {:?}",
                 add_peers, topic_hash
@@ -1000,13 +1096,13 @@ where
         }
 
         // check if we need to get more peers, which we randomly select
-        if added_peers.len() < self.config.mesh_n() {
+        if added_peers.len() < mesh_n {
             // get the peers
             let new_peers = get_random_peers(
                 &self.topic_peers,
                 &self.connected_peers,
                 topic_hash,
-                self.config.mesh_n() - added_peers.len(),
+                mesh_n - added_peers.len(),
                 |peer| {
                     !added_peers.contains(peer)
                         && !self.explicit_peers.contains(peer)
@@ -1213,6 +1309,9 @@ where
             interval; ignoring",
                 peer_id, *peer_have
             );
+            if let Some(metrics) = self.metrics.as_mut() {
+                metrics.register_ihave_truncated();
+            }
             return;
         }
 
@@ -1222,6 +1321,9 @@ where
                     "IHAVE: peer {} has already advertised too many messages ({}); ignoring",
                     peer_id, *iasked
                 );
+                if let Some(metrics) = self.metrics.as_mut() {
+                    metrics.register_ihave_truncated();
+                }
                 return;
             }
         }
@@ -1271,6 +1373,9 @@ where
             let mut iask = iwant_ids.len();
             if *iasked + iask > self.config.max_ihave_length() {
                 iask = self.config.max_ihave_length().saturating_sub(*iasked);
+                if let Some(metrics) = self.metrics.as_mut() {
+                    metrics.register_ihave_truncated();
+                }
             }
 
             // Send the list of IWANT control messages
@@ -1475,7 +1580,7 @@ where
 
                     // check mesh upper bound and only allow graft if the upper bound is not reached or
                     // if it is an outbound peer
-                    if peers.len() >= self.config.mesh_n_high()
+                    if peers.len() >= self.config.topic_mesh_params(&topic_hash).mesh_n_high
                         && !self.outbound_peers.contains(peer_id)
                     {
                         to_prune_topics.insert(topic_hash.clone());
@@ -1941,10 +2046,11 @@ where
         // Notify the application about the subscription, after the grafts are sent.
         let mut application_event = Vec::new();
 
-        let filtered_topics = match self
-            .subscription_filter
-            .filter_incoming_subscriptions(subscriptions, subscribed_topics)
-        {
+        let filtered_topics = match self.subscription_filter.filter_incoming_subscriptions_from(
+            propagation_source,
+            subscriptions,
+            subscribed_topics,
+        ) {
             Ok(topics) => topics,
             Err(s) => {
                 error!(
@@ -1993,7 +2099,7 @@ where
                             .is_backoff_with_slack(topic_hash, propagation_source)
                     {
                         if let Some(peers) = self.mesh.get_mut(topic_hash) {
-                            if peers.len() < self.config.mesh_n_low()
+                            if peers.len() < self.config.topic_mesh_params(topic_hash).mesh_n_low
                                 && peers.insert(*propagation_source)
                             {
                                 debug!(
@@ -2116,6 +2222,7 @@ where
         let start = Instant::now();
 
         self.heartbeat_ticks += 1;
+        self.last_heartbeat = Some(start);
 
         let mut to_graft = HashMap::new();
         let mut to_prune = HashMap::new();
@@ -2154,6 +2261,7 @@ where
             let backoffs = &self.backoffs;
             let topic_peers = &self.topic_peers;
             let outbound_peers = &self.outbound_peers;
+            let mesh_params = self.config.topic_mesh_params(topic_hash);
 
             // drop all peers with negative score, without PX
             // if there is at some point a stable retain method for BTreeSet the following can be
@@ -2190,15 +2298,15 @@ where
             }
 
             // too little peers - add some
-            if peers.len() < self.config.mesh_n_low() {
+            if peers.len() < mesh_params.mesh_n_low {
                 debug!(
                     "HEARTBEAT: Mesh low. Topic: {} Contains: {} needs: {}",
                     topic_hash,
                     peers.len(),
-                    self.config.mesh_n_low()
+                    mesh_params.mesh_n_low
                 );
                 // not enough peers - get mesh_n - current_length more
-                let desired_peers = self.config.mesh_n() - peers.len();
+                let desired_peers = mesh_params.mesh_n - peers.len();
                 let peer_list = get_random_peers(
                     topic_peers,
                     &self.connected_peers,
@@ -2224,14 +2332,14 @@ where
             }
 
             // too many peers - remove some
-            if peers.len() > self.config.mesh_n_high() {
+            if peers.len() > mesh_params.mesh_n_high {
                 debug!(
                     "HEARTBEAT: Mesh high. Topic: {} Contains: {} needs: {}",
                     topic_hash,
                     peers.len(),
-                    self.config.mesh_n_high()
+                    mesh_params.mesh_n_high
                 );
-                let excess_peer_no = peers.len() - self.config.mesh_n();
+                let excess_peer_no = peers.len() - mesh_params.mesh_n;
 
                 // shuffle the peers and then sort by score ascending beginning with the worst
                 let mut rng = thread_rng();
@@ -2285,7 +2393,7 @@ where
             }
 
             // do we have enough outbound peers?
-            if peers.len() >= self.config.mesh_n_low() {
+            if peers.len() >= mesh_params.mesh_n_low {
                 // count number of outbound peers we have
                 let outbound = { peers.iter().filter(|p| outbound_peers.contains(*p)).count() };
 
@@ -2414,6 +2522,7 @@ where
         // maintain fanout
         // check if our peers are still a part of the topic
         for (topic_hash, peers) in self.fanout.iter_mut() {
+            let mesh_n = self.config.topic_mesh_params(topic_hash).mesh_n;
             let mut to_remove_peers = Vec::new();
             let publish_threshold = match &self.peer_score {
                 Some((_, thresholds, _, _)) => thresholds.publish_threshold,
@@ -2443,13 +2552,13 @@ where
             }
 
             // not enough peers
-            if peers.len() < self.config.mesh_n() {
+            if peers.len() < mesh_n {
                 debug!(
                     "HEARTBEAT: Fanout low. Contains: {:?} needs: {:?}",
                     peers.len(),
-                    self.config.mesh_n()
+                    mesh_n
                 );
-                let needed_peers = self.config.mesh_n() - peers.len();
+                let needed_peers = mesh_n - peers.len();
                 let explicit_peers = &self.explicit_peers;
                 let new_peers = get_random_peers(
                     &self.topic_peers,
@@ -2493,7 +2602,10 @@ where
             })
         }
 
-        self.emit_gossip();
+        let ihave_sent = self.emit_gossip();
+
+        let grafts_sent = to_graft.values().map(Vec::len).sum();
+        let prunes_sent = to_prune.values().map(Vec::len).sum();
 
         // send graft/prunes
         if !to_graft.is_empty() | !to_prune.is_empty() {
@@ -2511,12 +2623,27 @@ where
             let duration = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
             metrics.observe_heartbeat_duration(duration);
         }
+
+        self.last_heartbeat_stats = Some(HeartbeatStats {
+            grafts_sent,
+            prunes_sent,
+            ihave_sent,
+            mesh_peers_per_topic: self
+                .mesh
+                .iter()
+                .map(|(topic, peers)| (topic.clone(), peers.len()))
+                .collect(),
+            duration: start.elapsed(),
+        });
     }
 
     /// Emits gossip - Send IHAVE messages to a random set of gossip peers. This is applied to mesh
     /// and fanout peers
-    fn emit_gossip(&mut self) {
+    ///
+    /// Returns the number of IHAVE messages sent.
+    fn emit_gossip(&mut self) -> usize {
         let mut rng = thread_rng();
+        let mut ihave_sent = 0;
         for (topic_hash, peers) in self.mesh.iter().chain(self.fanout.iter()) {
             let mut message_ids = self.mcache.get_gossip_message_ids(topic_hash);
             if message_ids.is_empty() {
@@ -2536,9 +2663,10 @@ where
             }
 
             // dynamic number of peers to gossip based on `gossip_factor` with minimum `gossip_lazy`
+            let gossip_lazy = self.config.topic_mesh_params(topic_hash).gossip_lazy;
             let n_map = |m| {
                 max(
-                    self.config.gossip_lazy(),
+                    gossip_lazy,
                     (self.config.gossip_factor() * m as f64) as usize,
                 )
             };
@@ -2577,8 +2705,11 @@ where
                         message_ids: peer_message_ids,
                     },
                 );
+                ihave_sent += 1;
             }
         }
+
+        ihave_sent
     }
 
     /// Handles multiple GRAFT/PRUNE messages and coalesces them into chunked gossip control
@@ -27,7 +27,8 @@ use crate::transform::{DataTransform, IdentityTransform};
 use crate::types::FastMessageId;
 use crate::ValidationError;
 use crate::{
-    config::Config, config::ConfigBuilder, IdentTopic as Topic, Message, TopicScoreParams,
+    config::Config, config::ConfigBuilder, config::TopicMeshParams, IdentTopic as Topic, Message,
+    TopicScoreParams,
 };
 use async_std::net::Ipv4Addr;
 use byteorder::{BigEndian, ByteOrder};
@@ -707,6 +708,127 @@ fn test_publish_without_flood_publishing() {
     );
 }
 
+/// Test that messages above the lazy publish threshold are announced via IHAVE and served on
+/// IWANT.
+#[test]
+fn test_lazy_publish() {
+    let config = ConfigBuilder::default()
+        .flood_publish(false)
+        .lazy_publish_threshold(64)
+        .build()
+        .unwrap();
+
+    let publish_topic = String::from("test_lazy_publish");
+    let (mut gs, peers, topic_hashes) = inject_nodes1()
+        .peer_no(2)
+        .topics(vec![publish_topic.clone()])
+        .to_subscribe(true)
+        .gs_config(config)
+        .create_network();
+    assert_eq!(gs.mesh[&topic_hashes[0]].len(), 2);
+
+    let collect_publishes = |gs: &mut Behaviour<_, _>| {
+        gs.events
+            .drain(..)
+            .fold(vec![], |mut collected_publish, e| match e {
+                ToSwarm::NotifyHandler {
+                    peer_id,
+                    event: HandlerIn::Message(ref message),
+                    ..
+                } => {
+                    let event = proto_to_message(message);
+                    for s in event.messages {
+                        collected_publish.push((peer_id, s));
+                    }
+                    collected_publish
+                }
+                _ => collected_publish,
+            })
+    };
+
+    // Small messages are sent in full.
+    gs.publish(Topic::new(publish_topic.clone()), vec![0; 42])
+        .unwrap();
+    assert_eq!(collect_publishes(&mut gs).len(), 2);
+
+    // Large messages are only announced.
+    let msg_id = gs.publish(Topic::new(publish_topic), vec![1; 100]).unwrap();
+    assert_eq!(
+        count_control_msgs(&gs, |_, action| matches!(
+            action,
+            ControlAction::IHave { message_ids, .. } if message_ids == &vec![msg_id.clone()]
+        )),
+        2,
+        "Should queue an IHAVE for both mesh peers"
+    );
+    assert!(collect_publishes(&mut gs).is_empty());
+
+    // The message is delivered when requested.
+    gs.handle_iwant(&peers[0], vec![msg_id]);
+    let publishes = collect_publishes(&mut gs);
+    assert_eq!(publishes.len(), 1);
+    assert_eq!(publishes[0].0, peers[0]);
+    assert_eq!(publishes[0].1.data, vec![1; 100]);
+}
+
+/// Tests that lazily published messages are announced in a single IHAVE per heartbeat, as peers
+/// ignore more than `max_ihave_messages` IHAVEs per heartbeat.
+#[test]
+fn test_lazy_publish_batches_ihaves() {
+    let config = ConfigBuilder::default()
+        .flood_publish(false)
+        .lazy_publish_threshold(64)
+        .build()
+        .unwrap();
+    let num_messages = config.max_ihave_messages() * 2;
+
+    let publish_topic = String::from("test_lazy_publish_batches_ihaves");
+    let (mut gs, peers, topic_hashes) = inject_nodes1()
+        .peer_no(2)
+        .topics(vec![publish_topic.clone()])
+        .to_subscribe(true)
+        .gs_config(config)
+        .create_network();
+    flush_events(&mut gs);
+
+    let msg_ids = (0..num_messages)
+        .map(|i| {
+            gs.publish(Topic::new(publish_topic.clone()), vec![i as u8; 100])
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        gs.events.is_empty(),
+        "Should not send anything before the heartbeat"
+    );
+
+    gs.heartbeat();
+
+    for peer in &peers {
+        let ihaves = gs
+            .events
+            .iter()
+            .filter_map(|e| match e {
+                ToSwarm::NotifyHandler {
+                    peer_id,
+                    event: HandlerIn::Message(ref m),
+                    ..
+                } if peer_id == peer => Some(proto_to_message(m)),
+                _ => None,
+            })
+            .flat_map(|rpc| rpc.control_msgs)
+            .filter_map(|control| match control {
+                ControlAction::IHave {
+                    topic_hash,
+                    message_ids,
+                } if topic_hash == topic_hashes[0] => Some(message_ids),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ihaves, vec![msg_ids.clone()]);
+    }
+}
+
 /// Test local node publish to unsubscribed topic
 #[test]
 fn test_fanout() {
@@ -746,12 +868,17 @@ fn test_fanout() {
 
     assert_eq!(
         gs.fanout
-            .get(&TopicHash::from_raw(fanout_topic))
+            .get(&TopicHash::from_raw(fanout_topic.clone()))
             .unwrap()
             .len(),
         gs.config.mesh_n(),
         "Fanout should contain `mesh_n` peers for fanout topic"
     );
+    assert_eq!(
+        gs.fanout_peers(&TopicHash::from_raw(fanout_topic)).count(),
+        gs.config.mesh_n(),
+        "Expected fanout_peers to list the fanout peers of the topic."
+    );
 
     // Collect all publish messages
     let publishes = gs
@@ -1796,6 +1923,112 @@ fn test_mesh_subtraction() {
     assert_eq!(gs.mesh.get(&topics[0]).unwrap().len(), config.mesh_n());
 }
 
+// Tests that the mesh of a topic is maintained with the topic's own mesh parameters
+#[test]
+fn test_mesh_maintenance_with_topic_mesh_params() {
+    let params = TopicMeshParams {
+        mesh_n: 10,
+        mesh_n_low: 8,
+        mesh_n_high: 14,
+        gossip_lazy: 10,
+    };
+    let config = ConfigBuilder::default()
+        .topic_mesh_params(Topic::new("custom").hash(), params)
+        .build()
+        .unwrap();
+
+    let (mut gs, _, topics) = inject_nodes1()
+        .peer_no(20)
+        .topics(vec!["custom".into(), "default".into()])
+        .to_subscribe(true)
+        .gs_config(config.clone())
+        .create_network();
+
+    // Subscribing peers are added to the mesh until mesh_n_low is reached.
+    assert_eq!(gs.mesh[&topics[0]].len(), params.mesh_n_low);
+    assert_eq!(gs.mesh[&topics[1]].len(), config.mesh_n_low());
+
+    // Prune a peer from each mesh, the heartbeat then fills them up to mesh_n.
+    for topic in &topics {
+        let peer = *gs.mesh[topic].iter().next().unwrap();
+        gs.handle_prune(&peer, vec![(topic.clone(), vec![], None)]);
+    }
+    gs.heartbeat();
+
+    assert_eq!(gs.mesh[&topics[0]].len(), params.mesh_n);
+    assert_eq!(gs.mesh[&topics[1]].len(), config.mesh_n());
+    assert_eq!(config.topic_mesh_params(&topics[0]), params);
+    assert_eq!(config.topic_mesh_params(&topics[1]).mesh_n, config.mesh_n());
+}
+
+#[test]
+fn test_topic_mesh_state() {
+    let config = ConfigBuilder::default()
+        .flood_publish(false)
+        .build()
+        .unwrap();
+
+    let (mut gs, _, topics) = inject_nodes1()
+        .peer_no(20)
+        .topics(vec!["mesh".into(), "fanout".into()])
+        .to_subscribe(true)
+        .gs_config(config)
+        .create_network();
+
+    gs.unsubscribe(&Topic::new("fanout")).unwrap();
+    gs.publish(Topic::new("mesh"), vec![0; 42]).unwrap();
+    gs.publish(Topic::new("fanout"), vec![1; 42]).unwrap();
+    gs.publish(Topic::new("fanout"), vec![2; 42]).unwrap();
+
+    let mesh_state = gs.topic_mesh_state(&topics[0]).unwrap();
+    assert_eq!(mesh_state.topic, topics[0]);
+    assert_eq!(mesh_state.mesh_peers, gs.mesh[&topics[0]].len());
+    assert_eq!(mesh_state.fanout_peers, 0);
+    assert_eq!(mesh_state.mcache_size, 1);
+    assert!(mesh_state.subscribed);
+    assert_eq!(mesh_state.last_heartbeat, None);
+
+    let fanout_state = gs.topic_mesh_state(&topics[1]).unwrap();
+    assert_eq!(fanout_state.topic, topics[1]);
+    assert_eq!(fanout_state.mesh_peers, 0);
+    assert_eq!(fanout_state.fanout_peers, gs.config.mesh_n());
+    assert_eq!(fanout_state.mcache_size, 2);
+    assert!(!fanout_state.subscribed);
+
+    assert!(gs.topic_mesh_state(&Topic::new("unknown").hash()).is_none());
+
+    gs.heartbeat();
+    let states = gs.all_topic_states().collect::<Vec<_>>();
+    assert_eq!(states.len(), 2);
+    assert!(states.iter().all(|s| s.last_heartbeat.is_some()));
+}
+
+#[test]
+fn test_last_heartbeat_stats() {
+    let (mut gs, _, topics) = inject_nodes1()
+        .peer_no(20)
+        .topics(vec!["topic".into()])
+        .to_subscribe(true)
+        .create_network();
+    assert!(gs.last_heartbeat_stats().is_none());
+
+    gs.publish(Topic::new("topic"), vec![0; 42]).unwrap();
+    let peer = *gs.mesh[&topics[0]].iter().next().unwrap();
+    gs.handle_prune(&peer, vec![(topics[0].clone(), vec![], None)]);
+    let mesh_peers = gs.mesh[&topics[0]].len();
+
+    gs.heartbeat();
+
+    let stats = gs.last_heartbeat_stats().unwrap();
+    assert_eq!(stats.grafts_sent, gs.config.mesh_n() - mesh_peers);
+    assert_eq!(stats.prunes_sent, 0);
+    assert!(stats.ihave_sent > 0);
+    assert_eq!(
+        stats.mesh_peers_per_topic,
+        HashMap::from([(topics[0].clone(), gs.config.mesh_n())])
+    );
+}
+
 #[test]
 fn test_connect_to_px_peers_on_handle_prune() {
     let config: Config = Config::default();
@@ -5062,6 +5295,18 @@ fn test_public_api() {
         peers,
         "Expected all_peers to contain all peers."
     );
+
+    let unknown_topic = TopicHash::from_raw("unknown");
+    assert_eq!(
+        gs.mesh_peers(&unknown_topic).count(),
+        0,
+        "Expected no mesh peers for an unknown topic."
+    );
+    assert_eq!(
+        gs.fanout_peers(&unknown_topic).count(),
+        0,
+        "Expected no fanout peers for an unknown topic."
+    );
 }
 
 #[test]
@@ -5159,6 +5404,64 @@ fn test_subscribe_to_invalid_topic() {
     assert!(gs.subscribe(&t2).is_err());
 }
 
+#[test]
+fn test_incoming_subscriptions_filtered_by_peer() {
+    /// Only allows subscriptions to `t1` from the peers in the set.
+    #[derive(Clone, Default)]
+    struct PeerSubscriptionFilter(HashSet<PeerId>);
+
+    impl TopicSubscriptionFilter for PeerSubscriptionFilter {
+        fn can_subscribe(&mut self, _topic_hash: &TopicHash) -> bool {
+            true
+        }
+
+        fn allow_incoming_subscription_from(
+            &mut self,
+            peer: &PeerId,
+            subscription: &Subscription,
+        ) -> bool {
+            subscription.topic_hash != Topic::new("t1").hash() || self.0.contains(peer)
+        }
+    }
+
+    let (mut gs, peers, topic_hashes) = inject_nodes::<IdentityTransform, _>()
+        .peer_no(2)
+        .topics(vec!["t1".into(), "t2".into()])
+        .subscription_filter(PeerSubscriptionFilter::default())
+        .to_subscribe(false)
+        .create_network();
+    gs.subscription_filter.0.insert(peers[0]);
+
+    let subscriptions = topic_hashes
+        .iter()
+        .map(|topic_hash| Subscription {
+            action: SubscriptionAction::Subscribe,
+            topic_hash: topic_hash.clone(),
+        })
+        .collect::<Vec<_>>();
+    gs.handle_received_subscriptions(&subscriptions, &peers[0]);
+    gs.handle_received_subscriptions(&subscriptions, &peers[1]);
+
+    assert_eq!(
+        gs.peer_topics[&peers[0]],
+        topic_hashes.iter().cloned().collect()
+    );
+    assert_eq!(
+        gs.peer_topics[&peers[1]],
+        vec![topic_hashes[1].clone()].into_iter().collect()
+    );
+    assert_eq!(
+        gs.topic_peers[&topic_hashes[0]],
+        vec![peers[0]].into_iter().collect()
+    );
+    assert!(
+        !gs.events
+            .iter()
+            .any(|e| matches!(e, ToSwarm::CloseConnection { .. })),
+        "Refused subscriptions should not close the connection"
+    );
+}
+
 #[test]
 fn test_subscribe_and_graft_with_negative_score() {
     //simulate a communication between two gossipsub instances
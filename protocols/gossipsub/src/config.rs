@@ -19,10 +19,12 @@
 // DEALINGS IN THE SOFTWARE.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use crate::protocol::{ProtocolConfig, ProtocolId, FLOODSUB_PROTOCOL};
+use crate::topic::TopicHash;
 use crate::types::{FastMessageId, Message, MessageId, PeerKind, RawMessage};
 
 use libp2p_identity::PeerId;
@@ -34,6 +36,10 @@ pub enum ValidationMode {
     /// This is the default setting. This requires the message author to be a valid [`PeerId`] and to
     /// be present as well as the sequence number. All messages must have valid signatures.
     ///
+    /// The signature is verified against the public key of the author, as specified by the
+    /// [pubsub message signing spec](https://github.com/libp2p/specs/tree/master/pubsub#message-signing),
+    /// before the message is passed on for validation by the application.
+    ///
     /// NOTE: This setting will reject messages from nodes using
     /// [`crate::behaviour::MessageAuthenticity::Anonymous`] and all messages that do not have
     /// signatures.
@@ -58,17 +64,34 @@ pub enum Version {
     V1_1,
 }
 
+/// Mesh degree parameters of a single topic, overriding the ones of the [`Config`].
+///
+/// See [`ConfigBuilder::topic_mesh_params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopicMeshParams {
+    /// Target number of peers for the mesh network (D in the spec).
+    pub mesh_n: usize,
+    /// Minimum number of peers in mesh network before adding more (D_lo in the spec).
+    pub mesh_n_low: usize,
+    /// Maximum number of peers in mesh network before removing some (D_high in the spec).
+    pub mesh_n_high: usize,
+    /// Minimum number of peers to emit gossip to during a heartbeat (D_lazy in the spec).
+    pub gossip_lazy: usize,
+}
+
 /// Configuration parameters that define the performance of the gossipsub network.
 #[derive(Clone)]
 pub struct Config {
     protocol: ProtocolConfig,
     history_length: usize,
     history_gossip: usize,
+    topic_history_gossip: HashMap<TopicHash, usize>,
     mesh_n: usize,
     mesh_n_low: usize,
     mesh_n_high: usize,
     retain_scores: usize,
     gossip_lazy: usize,
+    topic_mesh_params: HashMap<TopicHash, TopicMeshParams>,
     gossip_factor: f64,
     heartbeat_initial_delay: Duration,
     heartbeat_interval: Duration,
@@ -86,6 +109,7 @@ pub struct Config {
     unsubscribe_backoff: Duration,
     backoff_slack: u32,
     flood_publish: bool,
+    lazy_publish_threshold: usize,
     graft_flood_threshold: Duration,
     mesh_outbound_min: usize,
     opportunistic_graft_ticks: u64,
@@ -114,6 +138,21 @@ impl Config {
         self.history_gossip
     }
 
+    /// Number of past heartbeats to gossip about for the given topic.
+    ///
+    /// This is the value set through [`ConfigBuilder::topic_history_gossip`] or, if none was set
+    /// for the topic, [`Config::history_gossip`].
+    pub fn topic_history_gossip(&self, topic: &TopicHash) -> usize {
+        self.topic_history_gossip
+            .get(topic)
+            .copied()
+            .unwrap_or(self.history_gossip)
+    }
+
+    pub(crate) fn topic_history_gossip_overrides(&self) -> &HashMap<TopicHash, usize> {
+        &self.topic_history_gossip
+    }
+
     /// Target number of peers for the mesh network (D in the spec, default is 6).
     pub fn mesh_n(&self) -> usize {
         self.mesh_n
@@ -144,6 +183,23 @@ impl Config {
         self.gossip_lazy
     }
 
+    /// The mesh degree parameters used for the given topic.
+    ///
+    /// These are the ones set through [`ConfigBuilder::topic_mesh_params`] or, if none were set
+    /// for the topic, [`Config::mesh_n`], [`Config::mesh_n_low`], [`Config::mesh_n_high`] and
+    /// [`Config::gossip_lazy`].
+    pub fn topic_mesh_params(&self, topic: &TopicHash) -> TopicMeshParams {
+        self.topic_mesh_params
+            .get(topic)
+            .copied()
+            .unwrap_or(TopicMeshParams {
+                mesh_n: self.mesh_n,
+                mesh_n_low: self.mesh_n_low,
+                mesh_n_high: self.mesh_n_high,
+                gossip_lazy: self.gossip_lazy,
+            })
+    }
+
     /// Affects how many peers we will emit gossip to at each heartbeat.
     ///
     /// We will send gossip to `gossip_factor * (total number of non-mesh peers)`, or
@@ -301,6 +357,25 @@ impl Config {
         self.flood_publish
     }
 
+    /// Size in bytes above which newly published messages are announced lazily. Instead of
+    /// sending the full message, the publisher sends an IHAVE for it to the peers it would have
+    /// sent the message to, and those peers pull the message via IWANT.
+    ///
+    /// The IHAVEs are sent with the next heartbeat, batching all messages published since the
+    /// previous heartbeat into a single IHAVE per peer and topic. Peers request at most
+    /// [`Config::max_ihave_length`] messages per heartbeat, hence at most that many messages
+    /// should be published lazily per heartbeat.
+    ///
+    /// This trades latency, an extra round trip, for bandwidth, as peers that received the
+    /// message from elsewhere in the meantime don't request it. Explicit peers and floodsub
+    /// peers always receive the full message. Lazily published messages must be requested
+    /// while they are still in the message cache, see [`Config::history_length`].
+    ///
+    /// The default is `usize::MAX`, i.e. lazy publishing is disabled.
+    pub fn lazy_publish_threshold(&self) -> usize {
+        self.lazy_publish_threshold
+    }
+
     /// If a GRAFT comes before `graft_flood_threshold` has elapsed since the last PRUNE,
     /// then there is an extra score penalty applied to the peer through P7.
     pub fn graft_flood_threshold(&self) -> Duration {
@@ -396,11 +471,13 @@ impl Default for ConfigBuilder {
                 protocol: ProtocolConfig::default(),
                 history_length: 5,
                 history_gossip: 3,
+                topic_history_gossip: HashMap::new(),
                 mesh_n: 6,
                 mesh_n_low: 5,
                 mesh_n_high: 12,
                 retain_scores: 4,
                 gossip_lazy: 6, // default to mesh_n
+                topic_mesh_params: HashMap::new(),
                 gossip_factor: 0.25,
                 heartbeat_initial_delay: Duration::from_secs(5),
                 heartbeat_interval: Duration::from_secs(1),
@@ -431,6 +508,7 @@ impl Default for ConfigBuilder {
                 unsubscribe_backoff: Duration::from_secs(10),
                 backoff_slack: 1,
                 flood_publish: true,
+                lazy_publish_threshold: usize::MAX,
                 graft_flood_threshold: Duration::from_secs(10),
                 mesh_outbound_min: 2,
                 opportunistic_graft_ticks: 60,
@@ -515,17 +593,36 @@ impl ConfigBuilder {
     }
 
     /// Number of heartbeats to keep in the `memcache` (default is 5).
+    ///
+    /// Cached messages are forwarded on IWANT requests. A long history does not increase the
+    /// gossip sent to peers, that is bounded by [`ConfigBuilder::history_gossip`].
     pub fn history_length(&mut self, history_length: usize) -> &mut Self {
         self.config.history_length = history_length;
         self
     }
 
     /// Number of past heartbeats to gossip about (default is 3).
+    ///
+    /// Must not be greater than [`ConfigBuilder::history_length`], otherwise
+    /// [`ConfigBuilder::build`] fails.
     pub fn history_gossip(&mut self, history_gossip: usize) -> &mut Self {
         self.config.history_gossip = history_gossip;
         self
     }
 
+    /// Overrides [`ConfigBuilder::history_gossip`] for the given topic.
+    ///
+    /// Useful for topics with a very low message rate, where the global window would stop
+    /// advertising messages before most peers had a chance to see them. All topics share the
+    /// message cache, so the override must not be greater than [`ConfigBuilder::history_length`],
+    /// otherwise [`ConfigBuilder::build`] fails.
+    pub fn topic_history_gossip(&mut self, topic: TopicHash, history_gossip: usize) -> &mut Self {
+        self.config
+            .topic_history_gossip
+            .insert(topic, history_gossip);
+        self
+    }
+
     /// Target number of peers for the mesh network (D in the spec, default is 6).
     pub fn mesh_n(&mut self, mesh_n: usize) -> &mut Self {
         self.config.mesh_n = mesh_n;
@@ -561,6 +658,15 @@ impl ConfigBuilder {
         self
     }
 
+    /// Overrides the mesh degree parameters for the given topic.
+    ///
+    /// Topics without an override use [`Self::mesh_n`], [`Self::mesh_n_low`],
+    /// [`Self::mesh_n_high`] and [`Self::gossip_lazy`].
+    pub fn topic_mesh_params(&mut self, topic: TopicHash, params: TopicMeshParams) -> &mut Self {
+        self.config.topic_mesh_params.insert(topic, params);
+        self
+    }
+
     /// Affects how many peers we will emit gossip to at each heartbeat.
     ///
     /// We will send gossip to `gossip_factor * (total number of non-mesh peers)`, or
@@ -727,6 +833,15 @@ impl ConfigBuilder {
         self
     }
 
+    /// Size in bytes above which newly published messages are announced via IHAVE and pulled by
+    /// peers via IWANT instead of being sent in full. See [`Config::lazy_publish_threshold`].
+    ///
+    /// The default is `usize::MAX`, i.e. lazy publishing is disabled.
+    pub fn lazy_publish_threshold(&mut self, lazy_publish_threshold: usize) -> &mut Self {
+        self.config.lazy_publish_threshold = lazy_publish_threshold;
+        self
+    }
+
     /// If a GRAFT comes before `graft_flood_threshold` has elapsed since the last PRUNE,
     /// then there is an extra score penalty applied to the peer through P7.
     pub fn graft_flood_threshold(&mut self, graft_flood_threshold: Duration) -> &mut Self {
@@ -845,6 +960,18 @@ impl ConfigBuilder {
             );
         }
 
+        if self
+            .config
+            .topic_history_gossip
+            .values()
+            .any(|history_gossip| self.config.history_length < *history_gossip)
+        {
+            return Err(
+                "The history_length must be greater than or equal to the history_gossip \
+                length of every topic",
+            );
+        }
+
         if !(self.config.mesh_outbound_min <= self.config.mesh_n_low
             && self.config.mesh_n_low <= self.config.mesh_n
             && self.config.mesh_n <= self.config.mesh_n_high)
@@ -859,6 +986,22 @@ impl ConfigBuilder {
             );
         }
 
+        for params in self.config.topic_mesh_params.values() {
+            if !(self.config.mesh_outbound_min <= params.mesh_n_low
+                && params.mesh_n_low <= params.mesh_n
+                && params.mesh_n <= params.mesh_n_high)
+            {
+                return Err("The following inequality doesn't hold for a topic \
+                    mesh_outbound_min <= mesh_n_low <= mesh_n <= mesh_n_high");
+            }
+
+            if self.config.mesh_outbound_min * 2 > params.mesh_n {
+                return Err(
+                    "The following inequality doesn't hold for a topic mesh_outbound_min <= mesh_n / 2",
+                );
+            }
+        }
+
         if self.config.unsubscribe_backoff.as_millis() == 0 {
             return Err("The unsubscribe_backoff parameter should be positive.");
         }
@@ -877,11 +1020,13 @@ impl std::fmt::Debug for Config {
         let _ = builder.field("protocol", &self.protocol);
         let _ = builder.field("history_length", &self.history_length);
         let _ = builder.field("history_gossip", &self.history_gossip);
+        let _ = builder.field("topic_history_gossip", &self.topic_history_gossip);
         let _ = builder.field("mesh_n", &self.mesh_n);
         let _ = builder.field("mesh_n_low", &self.mesh_n_low);
         let _ = builder.field("mesh_n_high", &self.mesh_n_high);
         let _ = builder.field("retain_scores", &self.retain_scores);
         let _ = builder.field("gossip_lazy", &self.gossip_lazy);
+        let _ = builder.field("topic_mesh_params", &self.topic_mesh_params);
         let _ = builder.field("gossip_factor", &self.gossip_factor);
         let _ = builder.field("heartbeat_initial_delay", &self.heartbeat_initial_delay);
         let _ = builder.field("heartbeat_interval", &self.heartbeat_interval);
@@ -895,6 +1040,7 @@ impl std::fmt::Debug for Config {
         let _ = builder.field("prune_backoff", &self.prune_backoff);
         let _ = builder.field("backoff_slack", &self.backoff_slack);
         let _ = builder.field("flood_publish", &self.flood_publish);
+        let _ = builder.field("lazy_publish_threshold", &self.lazy_publish_threshold);
         let _ = builder.field("graft_flood_threshold", &self.graft_flood_threshold);
         let _ = builder.field("mesh_outbound_min", &self.mesh_outbound_min);
         let _ = builder.field("opportunistic_graft_ticks", &self.opportunistic_graft_ticks);
@@ -972,6 +1118,63 @@ mod test {
         assert_eq!(result, get_expected_message_id());
     }
 
+    #[test]
+    fn topic_mesh_params_are_validated() {
+        let result = ConfigBuilder::default()
+            .topic_mesh_params(
+                Topic::<IdentityHash>::new("topic").hash(),
+                TopicMeshParams {
+                    mesh_n: 4,
+                    mesh_n_low: 5,
+                    mesh_n_high: 12,
+                    gossip_lazy: 6,
+                },
+            )
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn history_gossip_must_not_exceed_history_length() {
+        let result = ConfigBuilder::default()
+            .history_length(3)
+            .history_gossip(4)
+            .build();
+        assert!(result.is_err());
+
+        let config = ConfigBuilder::default()
+            .history_length(100)
+            .history_gossip(2)
+            .build()
+            .unwrap();
+        assert_eq!(config.history_length(), 100);
+        assert_eq!(config.history_gossip(), 2);
+    }
+
+    #[test]
+    fn topic_history_gossip_must_not_exceed_history_length() {
+        let topic = Topic::<IdentityHash>::new("topic").hash();
+
+        let result = ConfigBuilder::default()
+            .history_length(5)
+            .topic_history_gossip(topic.clone(), 6)
+            .build();
+        assert!(result.is_err());
+
+        let config = ConfigBuilder::default()
+            .history_length(5)
+            .history_gossip(2)
+            .topic_history_gossip(topic.clone(), 5)
+            .build()
+            .unwrap();
+        assert_eq!(config.topic_history_gossip(&topic), 5);
+        assert_eq!(
+            config.topic_history_gossip(&Topic::<IdentityHash>::new("other").hash()),
+            2
+        );
+    }
+
     #[test]
     fn create_config_with_protocol_id_prefix() {
         let protocol_config = ConfigBuilder::default()
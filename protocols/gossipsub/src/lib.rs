@@ -157,7 +157,7 @@ mod transform;
 mod types;
 
 pub use self::behaviour::{Behaviour, Event, MessageAuthenticity};
-pub use self::config::{Config, ConfigBuilder, ValidationMode, Version};
+pub use self::config::{Config, ConfigBuilder, TopicMeshParams, ValidationMode, Version};
 pub use self::error::{PublishError, SubscriptionError, ValidationError};
 pub use self::metrics::Config as MetricsConfig;
 pub use self::peer_score::{
@@ -171,7 +171,10 @@ pub use self::subscription_filter::{
 };
 pub use self::topic::{Hasher, Topic, TopicHash};
 pub use self::transform::{DataTransform, IdentityTransform};
-pub use self::types::{FastMessageId, Message, MessageAcceptance, MessageId, RawMessage, Rpc};
+pub use self::types::{
+    FastMessageId, HeartbeatStats, Message, MessageAcceptance, MessageId, RawMessage, Rpc,
+    TopicMeshState,
+};
 
 pub type IdentTopic = Topic<self::topic::IdentityHash>;
 pub type Sha256Topic = Topic<self::topic::Sha256Hash>;
@@ -47,6 +47,8 @@ pub(crate) struct MessageCache {
     /// won't get gossiped anymore when shift got called `gossip` many times after inserting the
     /// message in the cache.
     gossip: usize,
+    /// Per-topic overrides of `gossip`.
+    topic_gossip: HashMap<TopicHash, usize>,
 }
 
 impl fmt::Debug for MessageCache {
@@ -55,6 +57,7 @@ impl fmt::Debug for MessageCache {
             .field("msgs", &self.msgs)
             .field("history", &self.history)
             .field("gossip", &self.gossip)
+            .field("topic_gossip", &self.topic_gossip)
             .finish()
     }
 }
@@ -64,12 +67,21 @@ impl MessageCache {
     pub(crate) fn new(gossip: usize, history_capacity: usize) -> Self {
         MessageCache {
             gossip,
+            topic_gossip: HashMap::default(),
             msgs: HashMap::default(),
             iwant_counts: HashMap::default(),
             history: vec![Vec::new(); history_capacity],
         }
     }
 
+    /// Sets the number of history indices used for gossiping messages of the given topics.
+    ///
+    /// Overrides must not exceed the history capacity.
+    pub(crate) fn with_topic_gossip(mut self, topic_gossip: HashMap<TopicHash, usize>) -> Self {
+        self.topic_gossip = topic_gossip;
+        self
+    }
+
     /// Put a message into the memory cache.
     ///
     /// Returns true if the message didn't already exist in the cache.
@@ -155,7 +167,8 @@ impl MessageCache {
 
     /// Get a list of [`MessageId`]s for a given topic.
     pub(crate) fn get_gossip_message_ids(&self, topic: &TopicHash) -> Vec<MessageId> {
-        self.history[..self.gossip]
+        let gossip = self.topic_gossip.get(topic).copied().unwrap_or(self.gossip);
+        self.history[..gossip]
             .iter()
             .fold(vec![], |mut current_entries, entries| {
                 // search for entries with desired topic
@@ -182,6 +195,15 @@ impl MessageCache {
             })
     }
 
+    /// Get the number of cached messages for a given topic.
+    pub(crate) fn topic_message_count(&self, topic: &TopicHash) -> usize {
+        self.history
+            .iter()
+            .flatten()
+            .filter(|entry| &entry.topic == topic)
+            .count()
+    }
+
     /// Shift the history array down one and delete messages associated with the
     /// last entry.
     pub(crate) fn shift(&mut self) {
@@ -385,4 +407,22 @@ mod tests {
         assert_eq!(mc.history[0].len(), 0);
         assert_eq!(mc.msgs.len(), 0);
     }
+
+    #[test]
+    /// Test that a per-topic gossip window overrides the global one.
+    fn test_topic_gossip_window() {
+        let slow_topic = Topic::new("slow").hash();
+        let fast_topic = Topic::new("fast").hash();
+        let mut mc = new_cache(1, 5).with_topic_gossip(HashMap::from([(slow_topic.clone(), 4)]));
+
+        for (i, topic) in [&slow_topic, &fast_topic].into_iter().enumerate() {
+            let (id, m) = gen_testm(i as u64, topic.clone());
+            mc.put(&id, m);
+            mc.validate(&id);
+        }
+        mc.shift();
+
+        assert_eq!(mc.get_gossip_message_ids(&slow_topic).len(), 1);
+        assert!(mc.get_gossip_message_ids(&fast_topic).is_empty());
+    }
 }
@@ -174,6 +174,9 @@ pub(crate) struct Metrics {
     /// The number of times we have decided that an IWANT control message is required for this
     /// topic. A very high metric might indicate an underperforming network.
     topic_iwant_msgs: Family<TopicHash, Counter>,
+    /// The number of IHAVE control messages that were ignored or truncated because the remote
+    /// exceeded `max_ihave_messages` or `max_ihave_length` within a heartbeat.
+    ihave_truncated: Counter,
 }
 
 impl Metrics {
@@ -301,6 +304,15 @@ impl Metrics {
             );
             metric
         };
+        let ihave_truncated = {
+            let metric = Counter::default();
+            registry.register(
+                "ihave_truncated",
+                "Number of IHAVE messages ignored or truncated due to the configured IHAVE limits",
+                metric.clone(),
+            );
+            metric
+        };
 
         Self {
             max_topics,
@@ -327,6 +339,7 @@ impl Metrics {
             heartbeat_duration,
             memcache_misses,
             topic_iwant_msgs,
+            ihave_truncated,
         }
     }
 
@@ -490,6 +503,11 @@ impl Metrics {
         self.memcache_misses.inc();
     }
 
+    /// Register an IHAVE msg that was ignored or truncated due to the IHAVE limits.
+    pub(crate) fn register_ihave_truncated(&mut self) {
+        self.ihave_truncated.inc();
+    }
+
     /// Register sending an IWANT msg for this topic.
     pub(crate) fn register_iwant(&mut self, topic: &TopicHash) {
         if self.register_topic(topic).is_ok() {
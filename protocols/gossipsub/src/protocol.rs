@@ -611,6 +611,40 @@ mod tests {
         QuickCheck::new().quickcheck(prop as fn(_) -> _)
     }
 
+    #[test]
+    /// Test that messages whose content was altered after signing are rejected in strict mode.
+    fn reject_tampered_signed_message() {
+        fn prop(message: Message) {
+            let mut message = message.0;
+            message.data.push(0);
+
+            let rpc = Rpc {
+                messages: vec![message],
+                subscriptions: vec![],
+                control_msgs: vec![],
+            };
+
+            let mut codec = GossipsubCodec::new(codec::UviBytes::default(), ValidationMode::Strict);
+            let mut buf = BytesMut::new();
+            codec.encode(rpc.into_protobuf(), &mut buf).unwrap();
+            match codec.decode(&mut buf).unwrap().unwrap() {
+                HandlerEvent::Message {
+                    rpc,
+                    invalid_messages,
+                } => {
+                    assert!(rpc.messages.is_empty());
+                    assert!(matches!(
+                        invalid_messages[..],
+                        [(_, ValidationError::InvalidSignature)]
+                    ));
+                }
+                _ => panic!("Must decode a message"),
+            }
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(_) -> _)
+    }
+
     #[test]
     fn support_floodsub_with_custom_protocol() {
         let protocol_config = ConfigBuilder::default()
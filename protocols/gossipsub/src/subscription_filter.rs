@@ -20,6 +20,7 @@
 
 use crate::types::Subscription;
 use crate::TopicHash;
+use libp2p_identity::PeerId;
 use log::debug;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
@@ -81,6 +82,40 @@ pub trait TopicSubscriptionFilter {
     fn allow_incoming_subscription(&mut self, subscription: &Subscription) -> bool {
         self.can_subscribe(&subscription.topic_hash)
     }
+
+    /// Filters a list of incoming subscriptions received from the given peer.
+    /// By default this calls [`Self::filter_incoming_subscriptions`] and retains the
+    /// subscriptions [`Self::allow_incoming_subscription_from`] the peer allows.
+    fn filter_incoming_subscriptions_from<'a>(
+        &mut self,
+        peer: &PeerId,
+        subscriptions: &'a [Subscription],
+        currently_subscribed_topics: &BTreeSet<TopicHash>,
+    ) -> Result<HashSet<&'a Subscription>, String> {
+        let mut subscriptions =
+            self.filter_incoming_subscriptions(subscriptions, currently_subscribed_topics)?;
+        subscriptions.retain(|s| {
+            if self.allow_incoming_subscription_from(peer, s) {
+                true
+            } else {
+                debug!("Filtered incoming subscription {:?} from peer {}", s, peer);
+                false
+            }
+        });
+        Ok(subscriptions)
+    }
+
+    /// Returns true iff we allow an incoming subscription from the given peer, e.g. to reserve
+    /// a topic for a known set of peers.
+    /// A refused subscription is ignored as if it was never received.
+    /// By default this allows all subscriptions.
+    fn allow_incoming_subscription_from(
+        &mut self,
+        _peer: &PeerId,
+        _subscription: &Subscription,
+    ) -> bool {
+        true
+    }
 }
 
 //some useful implementers
@@ -157,6 +192,15 @@ impl<T: TopicSubscriptionFilter> TopicSubscriptionFilter for MaxCountSubscriptio
 
         Ok(result)
     }
+
+    fn allow_incoming_subscription_from(
+        &mut self,
+        peer: &PeerId,
+        subscription: &Subscription,
+    ) -> bool {
+        self.filter
+            .allow_incoming_subscription_from(peer, subscription)
+    }
 }
 
 /// Combines two subscription filters
@@ -185,6 +229,18 @@ where
         self.filter2
             .filter_incoming_subscription_set(intermediate, currently_subscribed_topics)
     }
+
+    fn allow_incoming_subscription_from(
+        &mut self,
+        peer: &PeerId,
+        subscription: &Subscription,
+    ) -> bool {
+        self.filter1
+            .allow_incoming_subscription_from(peer, subscription)
+            && self
+                .filter2
+                .allow_incoming_subscription_from(peer, subscription)
+    }
 }
 
 pub struct CallbackSubscriptionFilter<T>(pub T)
@@ -380,6 +436,48 @@ mod test {
         assert_eq!(result, subscriptions[1..].iter().collect());
     }
 
+    #[test]
+    fn test_filter_incoming_max_subscribed_forwards_peer_filter() {
+        /// Only allows subscriptions from the given peer.
+        struct PeerFilter(PeerId);
+
+        impl TopicSubscriptionFilter for PeerFilter {
+            fn can_subscribe(&mut self, _: &TopicHash) -> bool {
+                true
+            }
+
+            fn allow_incoming_subscription_from(
+                &mut self,
+                peer: &PeerId,
+                _: &Subscription,
+            ) -> bool {
+                peer == &self.0
+            }
+        }
+
+        let allowed_peer = PeerId::random();
+        let mut filter = MaxCountSubscriptionFilter {
+            filter: PeerFilter(allowed_peer),
+            max_subscribed_topics: 2,
+            max_subscriptions_per_request: 2,
+        };
+
+        let subscriptions = vec![Subscription {
+            action: Subscribe,
+            topic_hash: TopicHash::from_raw("t1"),
+        }];
+
+        let result = filter
+            .filter_incoming_subscriptions_from(&allowed_peer, &subscriptions, &BTreeSet::new())
+            .unwrap();
+        assert_eq!(result, subscriptions.iter().collect());
+
+        let result = filter
+            .filter_incoming_subscriptions_from(&PeerId::random(), &subscriptions, &BTreeSet::new())
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn test_callback_filter() {
         let t1 = TopicHash::from_raw("t1");
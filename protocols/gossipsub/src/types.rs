@@ -20,12 +20,15 @@
 
 //! A collection of types using the Gossipsub system.
 use crate::TopicHash;
+use instant::Instant;
 use libp2p_identity::PeerId;
 use libp2p_swarm::ConnectionId;
 use prometheus_client::encoding::EncodeLabelValue;
 use quick_protobuf::MessageWrite;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
+use std::time::Duration;
 
 use crate::rpc_proto::proto;
 #[cfg(feature = "serde")]
@@ -45,7 +48,8 @@ pub enum MessageAcceptance {
 
 /// Macro for declaring message id types
 macro_rules! declare_message_id_type {
-    ($name: ident, $name_string: expr) => {
+    ($(#[$meta:meta])* $name: ident, $name_string: expr) => {
+        $(#[$meta])*
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
         pub struct $name(pub Vec<u8>);
@@ -76,16 +80,31 @@ macro_rules! declare_message_id_type {
     };
 }
 
-// A type for gossipsub message ids.
-declare_message_id_type!(MessageId, "MessageId");
-
-// A type for gossipsub fast messsage ids, not to confuse with "real" message ids.
-//
-// A fast-message-id is an optional message_id that can be used to filter duplicates quickly. On
-// high intensive networks with lots of messages, where the message_id is based on the result of
-// decompressed traffic, it is beneficial to specify a `fast-message-id` that can identify and
-// filter duplicates quickly without performing the overhead of decompression.
-declare_message_id_type!(FastMessageId, "FastMessageId");
+declare_message_id_type!(
+    /// A type for gossipsub message ids.
+    ///
+    /// Message ids are computed by [`Config::message_id`](crate::Config::message_id), which can be
+    /// customised via [`ConfigBuilder::message_id_fn`](crate::ConfigBuilder::message_id_fn). A
+    /// `MessageId` can be constructed from any bytes via [`MessageId::new`] or [`From`], e.g. to
+    /// pass the id of a custom scheme to
+    /// [`Behaviour::report_message_validation_result`](crate::Behaviour::report_message_validation_result).
+    ///
+    /// Message ids are used to deduplicate messages: messages with the same id are treated as
+    /// duplicates, hence all peers of a network must derive the same id for the same message.
+    MessageId,
+    "MessageId"
+);
+
+declare_message_id_type!(
+    /// A type for gossipsub fast messsage ids, not to confuse with "real" message ids.
+    ///
+    /// A fast-message-id is an optional message_id that can be used to filter duplicates quickly. On
+    /// high intensive networks with lots of messages, where the message_id is based on the result of
+    /// decompressed traffic, it is beneficial to specify a `fast-message-id` that can identify and
+    /// filter duplicates quickly without performing the overhead of decompression.
+    FastMessageId,
+    "FastMessageId"
+);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct PeerConnections {
@@ -205,6 +224,41 @@ pub struct PeerInfo {
     //pub signed_peer_record: ?,
 }
 
+/// A snapshot of the local state of a topic, as returned by
+/// [`Behaviour::topic_mesh_state`](crate::Behaviour::topic_mesh_state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicMeshState {
+    /// The topic.
+    pub topic: TopicHash,
+    /// The number of peers in our mesh for the topic.
+    pub mesh_peers: usize,
+    /// The number of fanout peers for the topic, i.e. peers we publish to without being
+    /// subscribed.
+    pub fanout_peers: usize,
+    /// The number of messages of the topic in the message cache.
+    pub mcache_size: usize,
+    /// Whether we are subscribed to the topic.
+    pub subscribed: bool,
+    /// When the last heartbeat ran, `None` if it hasn't run yet.
+    pub last_heartbeat: Option<Instant>,
+}
+
+/// A summary of a heartbeat, as returned by
+/// [`Behaviour::last_heartbeat_stats`](crate::Behaviour::last_heartbeat_stats).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatStats {
+    /// The number of GRAFTs sent, counting each topic of a GRAFT message.
+    pub grafts_sent: usize,
+    /// The number of PRUNEs sent, counting each topic of a PRUNE message.
+    pub prunes_sent: usize,
+    /// The number of IHAVEs sent, counting each topic of an IHAVE message.
+    pub ihave_sent: usize,
+    /// The number of mesh peers per subscribed topic after the heartbeat.
+    pub mesh_peers_per_topic: HashMap<TopicHash, usize>,
+    /// How long the heartbeat took.
+    pub duration: Duration,
+}
+
 /// A Control message received by the gossipsub system.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ControlAction {
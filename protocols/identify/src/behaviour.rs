@@ -23,7 +23,9 @@ use crate::protocol::{Info, UpgradeError};
 use libp2p_core::{multiaddr, ConnectedPoint, Endpoint, Multiaddr};
 use libp2p_identity::PeerId;
 use libp2p_identity::PublicKey;
-use libp2p_swarm::behaviour::{ConnectionClosed, ConnectionEstablished, DialFailure, FromSwarm};
+use libp2p_swarm::behaviour::{
+    AddressChange, ConnectionClosed, ConnectionEstablished, DialFailure, FromSwarm,
+};
 use libp2p_swarm::{
     ConnectionDenied, DialError, ExternalAddresses, ListenAddresses, NetworkBehaviour,
     NotifyHandler, PollParameters, StreamUpgradeError, THandlerInEvent, ToSwarm,
@@ -33,7 +35,9 @@ use lru::LruCache;
 use std::num::NonZeroUsize;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
+    fmt,
     iter::FromIterator,
+    sync::Arc,
     task::Context,
     task::Poll,
     time::Duration,
@@ -52,6 +56,8 @@ pub struct Behaviour {
     events: VecDeque<ToSwarm<Event, InEvent>>,
     /// The addresses of all peers that we have discovered.
     discovered_peers: PeerCache,
+    /// The most recent [`Info`] received from each connected peer.
+    infos: HashMap<PeerId, Info>,
 
     listen_addresses: ListenAddresses,
     external_addresses: ExternalAddresses,
@@ -101,6 +107,33 @@ pub struct Config {
     ///
     /// Disabled by default.
     pub cache_size: usize,
+
+    /// Filter deciding which of the locally supported protocols are announced to peers.
+    ///
+    /// Announces all protocols by default.
+    protocol_filter: Option<ProtocolFilter>,
+
+    /// The maximum number of identification requests a remote may send per minute on a
+    /// connection.
+    ///
+    /// Defaults to 4.
+    max_inbound_requests_per_minute: u32,
+}
+
+/// Filter applied to the locally supported protocols before announcing them.
+#[derive(Clone)]
+pub struct ProtocolFilter(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl ProtocolFilter {
+    pub(crate) fn announces(&self, protocol: &str) -> bool {
+        (self.0)(protocol)
+    }
+}
+
+impl fmt::Debug for ProtocolFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProtocolFilter").finish()
+    }
 }
 
 impl Config {
@@ -116,6 +149,8 @@ impl Config {
             interval: Duration::from_secs(5 * 60),
             push_listen_addr_updates: false,
             cache_size: 100,
+            protocol_filter: None,
+            max_inbound_requests_per_minute: 4,
         }
     }
 
@@ -156,6 +191,29 @@ impl Config {
         self.cache_size = cache_size;
         self
     }
+
+    /// Configures which of the locally supported protocols are announced to peers.
+    ///
+    /// Only protocols for which the given filter returns `true` are announced.
+    /// Protocols that are filtered out are still supported, they are just not advertised.
+    pub fn with_protocol_filter(
+        mut self,
+        filter: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.protocol_filter = Some(ProtocolFilter(Arc::new(filter)));
+        self
+    }
+
+    /// Configures how many identification requests a remote may send per minute on a
+    /// connection.
+    ///
+    /// Streams opened for further requests within the minute are dropped without a reply. This
+    /// protects against peers making us serialize and send our identify information over and
+    /// over. Pushes from the remote are not limited.
+    pub fn with_max_inbound_requests_per_minute(mut self, max: u32) -> Self {
+        self.max_inbound_requests_per_minute = max;
+        self
+    }
 }
 
 impl Behaviour {
@@ -171,6 +229,7 @@ impl Behaviour {
             connected: HashMap::new(),
             events: VecDeque::new(),
             discovered_peers,
+            infos: HashMap::new(),
             listen_addresses: Default::default(),
             external_addresses: Default::default(),
         }
@@ -195,6 +254,13 @@ impl Behaviour {
         }
     }
 
+    /// Returns the most recent [`Info`] received from the given peer.
+    ///
+    /// The information is kept as long as we are connected to the peer.
+    pub fn info(&self, peer_id: &PeerId) -> Option<&Info> {
+        self.infos.get(peer_id)
+    }
+
     fn on_connection_established(
         &mut self,
         ConnectionEstablished {
@@ -252,6 +318,8 @@ impl NetworkBehaviour for Behaviour {
             self.config.agent_version.clone(),
             remote_addr.clone(),
             self.all_addresses(),
+            self.config.protocol_filter.clone(),
+            self.config.max_inbound_requests_per_minute,
         ))
     }
 
@@ -272,6 +340,8 @@ impl NetworkBehaviour for Behaviour {
             self.config.agent_version.clone(),
             addr.clone(), // TODO: This is weird? That is the public address we dialed, shouldn't need to tell the other party?
             self.all_addresses(),
+            self.config.protocol_filter.clone(),
+            self.config.max_inbound_requests_per_minute,
         ))
     }
 
@@ -292,6 +362,7 @@ impl NetworkBehaviour for Behaviour {
                     .put(peer_id, info.listen_addrs.iter().cloned());
 
                 let observed = info.observed_addr.clone();
+                self.infos.insert(peer_id, info.clone());
                 self.events
                     .push_back(ToSwarm::GenerateEvent(Event::Received { peer_id, info }));
                 self.events
@@ -382,6 +453,7 @@ impl NetworkBehaviour for Behaviour {
             }) => {
                 if remaining_established == 0 {
                     self.connected.remove(&peer_id);
+                    self.infos.remove(&peer_id);
                 } else if let Some(addrs) = self.connected.get_mut(&peer_id) {
                     addrs.remove(&connection_id);
                 }
@@ -395,9 +467,22 @@ impl NetworkBehaviour for Behaviour {
                     }
                 }
             }
+            FromSwarm::AddressChange(AddressChange {
+                peer_id,
+                connection_id,
+                new,
+                ..
+            }) => {
+                if let Some(addr) = self
+                    .connected
+                    .get_mut(&peer_id)
+                    .and_then(|addrs| addrs.get_mut(&connection_id))
+                {
+                    *addr = new.get_remote_address().clone();
+                }
+            }
             FromSwarm::NewListenAddr(_)
             | FromSwarm::ExpiredListenAddr(_)
-            | FromSwarm::AddressChange(_)
             | FromSwarm::ListenFailure(_)
             | FromSwarm::NewListener(_)
             | FromSwarm::ListenerError(_)
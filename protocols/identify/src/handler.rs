@@ -18,19 +18,21 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use crate::behaviour::ProtocolFilter;
 use crate::protocol::{Info, PushInfo, UpgradeError};
 use crate::{protocol, PROTOCOL_NAME, PUSH_PROTOCOL_NAME};
 use either::Either;
 use futures::prelude::*;
 use futures_bounded::Timeout;
 use futures_timer::Delay;
+use instant::Instant;
 use libp2p_core::upgrade::{ReadyUpgrade, SelectUpgrade};
 use libp2p_core::Multiaddr;
 use libp2p_identity::PeerId;
 use libp2p_identity::PublicKey;
 use libp2p_swarm::handler::{
-    ConnectionEvent, DialUpgradeError, FullyNegotiatedInbound, FullyNegotiatedOutbound,
-    ProtocolSupport,
+    AddressChange, ConnectionEvent, DialUpgradeError, FullyNegotiatedInbound,
+    FullyNegotiatedOutbound, ProtocolSupport,
 };
 use libp2p_swarm::{
     ConnectionHandler, ConnectionHandlerEvent, KeepAlive, StreamProtocol, StreamUpgradeError,
@@ -38,7 +40,7 @@ use libp2p_swarm::{
 };
 use log::{warn, Level};
 use smallvec::SmallVec;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::{io, task::Context, task::Poll, time::Duration};
 
 const STREAM_TIMEOUT: Duration = Duration::from_secs(60);
@@ -92,6 +94,46 @@ pub struct Handler {
     local_supported_protocols: SupportedProtocols,
     remote_supported_protocols: HashSet<StreamProtocol>,
     external_addresses: HashSet<Multiaddr>,
+
+    /// Filter deciding which local protocols are announced to the remote.
+    protocol_filter: Option<ProtocolFilter>,
+
+    /// Limits the identification requests the remote may send us.
+    inbound_request_limit: InboundRequestLimit,
+}
+
+/// Sliding window limiting the number of inbound identification requests per minute.
+#[derive(Debug)]
+struct InboundRequestLimit {
+    max_per_minute: u32,
+    /// When the requests within the last minute were accepted.
+    accepted: VecDeque<Instant>,
+}
+
+impl InboundRequestLimit {
+    fn new(max_per_minute: u32) -> Self {
+        Self {
+            max_per_minute,
+            accepted: VecDeque::new(),
+        }
+    }
+
+    /// Returns whether a request arriving at `now` is within the limit, recording it if so.
+    fn try_accept(&mut self, now: Instant) -> bool {
+        while let Some(accepted) = self.accepted.front() {
+            if now.duration_since(*accepted) < Duration::from_secs(60) {
+                break;
+            }
+            self.accepted.pop_front();
+        }
+
+        if self.accepted.len() >= self.max_per_minute as usize {
+            return false;
+        }
+
+        self.accepted.push_back(now);
+        true
+    }
 }
 
 /// An event from `Behaviour` with the information requested by the `Handler`.
@@ -127,6 +169,8 @@ impl Handler {
         agent_version: String,
         observed_addr: Multiaddr,
         external_addresses: HashSet<Multiaddr>,
+        protocol_filter: Option<ProtocolFilter>,
+        max_inbound_requests_per_minute: u32,
     ) -> Self {
         Self {
             remote_peer_id,
@@ -146,6 +190,8 @@ impl Handler {
             remote_supported_protocols: HashSet::default(),
             remote_info: Default::default(),
             external_addresses,
+            protocol_filter,
+            inbound_request_limit: InboundRequestLimit::new(max_inbound_requests_per_minute),
         }
     }
 
@@ -160,6 +206,14 @@ impl Handler {
     ) {
         match output {
             future::Either::Left(stream) => {
+                if !self.inbound_request_limit.try_accept(Instant::now()) {
+                    warn!(
+                        "Dropping inbound identify stream from {} because it exceeds the limit of {} requests per minute",
+                        self.remote_peer_id, self.inbound_request_limit.max_per_minute
+                    );
+                    return;
+                }
+
                 let info = self.build_info();
 
                 if self
@@ -227,7 +281,16 @@ impl Handler {
             protocol_version: self.protocol_version.clone(),
             agent_version: self.agent_version.clone(),
             listen_addrs: Vec::from_iter(self.external_addresses.iter().cloned()),
-            protocols: Vec::from_iter(self.local_supported_protocols.iter().cloned()),
+            protocols: self
+                .local_supported_protocols
+                .iter()
+                .filter(|p| {
+                    self.protocol_filter
+                        .as_ref()
+                        .map_or(true, |filter| filter.announces(p.as_ref()))
+                })
+                .cloned()
+                .collect(),
             observed_addr: self.observed_addr.clone(),
         }
     }
@@ -412,9 +475,10 @@ impl ConnectionHandler for Handler {
                 ));
                 self.trigger_next_identify.reset(self.interval);
             }
-            ConnectionEvent::AddressChange(_)
-            | ConnectionEvent::ListenUpgradeError(_)
-            | ConnectionEvent::RemoteProtocolsChange(_) => {}
+            ConnectionEvent::AddressChange(AddressChange { new_address }) => {
+                self.observed_addr = new_address.clone();
+            }
+            ConnectionEvent::ListenUpgradeError(_) | ConnectionEvent::RemoteProtocolsChange(_) => {}
             ConnectionEvent::LocalProtocolsChange(change) => {
                 let before = log::log_enabled!(Level::Debug)
                     .then(|| self.local_protocols_to_string())
@@ -449,3 +513,30 @@ enum Success {
     SentIdentifyPush,
     ReceivedIdentifyPush(PushInfo),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inbound_requests_beyond_limit_are_rejected() {
+        let mut limit = InboundRequestLimit::new(4);
+        let start = Instant::now();
+
+        // 100 requests per second.
+        let accepted = (0..100)
+            .filter(|i| limit.try_accept(start + Duration::from_millis(i * 10)))
+            .count();
+        assert_eq!(accepted, 4);
+
+        // Requests are accepted again once the first ones are a minute old.
+        let later = start + Duration::from_secs(60);
+        assert!(limit.try_accept(later));
+        assert_eq!(
+            (0..10)
+                .filter(|i| limit.try_accept(later + Duration::from_millis(i * 10)))
+                .count(),
+            3
+        );
+    }
+}
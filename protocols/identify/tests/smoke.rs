@@ -78,6 +78,37 @@ async fn periodic_identify() {
     }
 }
 
+#[async_std::test]
+async fn only_announces_filtered_protocols() {
+    let _ = env_logger::try_init();
+
+    let mut swarm1 = Swarm::new_ephemeral(|identity| {
+        identify::Behaviour::new(identify::Config::new("a".to_string(), identity.public()))
+    });
+    let mut swarm2 = Swarm::new_ephemeral(|identity| {
+        identify::Behaviour::new(
+            identify::Config::new("a".to_string(), identity.public())
+                .with_protocol_filter(|protocol| protocol != identify::PUSH_PROTOCOL_NAME.as_ref()),
+        )
+    });
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let swarm1_received_info = loop {
+        if let identify::Event::Received { info, .. } = swarm1.next_behaviour_event().await {
+            break info;
+        }
+    };
+
+    assert!(swarm1_received_info
+        .protocols
+        .contains(&identify::PROTOCOL_NAME));
+    assert!(!swarm1_received_info
+        .protocols
+        .contains(&identify::PUSH_PROTOCOL_NAME));
+}
+
 #[async_std::test]
 async fn identify_push() {
     let _ = env_logger::try_init();
@@ -128,6 +159,50 @@ async fn identify_push() {
     assert!(swarm1_received_info.listen_addrs.is_empty());
 }
 
+#[async_std::test]
+async fn info_is_kept_while_connected() {
+    let _ = env_logger::try_init();
+
+    let mut swarm1 = Swarm::new_ephemeral(|identity| {
+        identify::Behaviour::new(identify::Config::new("a".to_string(), identity.public()))
+    });
+    let mut swarm2 = Swarm::new_ephemeral(|identity| {
+        identify::Behaviour::new(
+            identify::Config::new("a".to_string(), identity.public())
+                .with_agent_version("b".to_string()),
+        )
+    });
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let swarm1_peer_id = *swarm1.local_peer_id();
+    let swarm2_peer_id = *swarm2.local_peer_id();
+    assert!(swarm1.behaviour().info(&swarm2_peer_id).is_none());
+
+    swarm1
+        .wait(|event| {
+            matches!(
+                event,
+                SwarmEvent::Behaviour(identify::Event::Received { .. })
+            )
+            .then_some(())
+        })
+        .await;
+
+    let info = swarm1.behaviour().info(&swarm2_peer_id).unwrap();
+    assert_eq!(info.agent_version, "b");
+    assert!(swarm1.behaviour().info(&swarm1_peer_id).is_none());
+
+    async_std::task::spawn(swarm2.loop_on_next());
+    swarm1.disconnect_peer_id(swarm2_peer_id).unwrap();
+    swarm1
+        .wait(|event| matches!(event, SwarmEvent::ConnectionClosed { .. }).then_some(()))
+        .await;
+
+    assert!(swarm1.behaviour().info(&swarm2_peer_id).is_none());
+}
+
 #[async_std::test]
 async fn discover_peer_after_disconnect() {
     let _ = env_logger::try_init();
@@ -27,7 +27,7 @@ use crate::handler::{Handler, HandlerEvent, HandlerIn, RequestId};
 use crate::jobs::*;
 use crate::kbucket::{self, Distance, KBucketsTable, NodeStatus};
 use crate::protocol::{ConnectionType, KadPeer, ProtocolConfig};
-use crate::query::{Query, QueryConfig, QueryId, QueryPool, QueryPoolState};
+use crate::query::{Query, QueryConfig, QueryId, QueryOpts, QueryPool, QueryPoolState};
 use crate::record_priv::{
     self,
     store::{self, RecordStore},
@@ -111,6 +111,9 @@ pub struct Behaviour<TStore> {
     /// See [`Config::caching`].
     caching: Caching,
 
+    /// See [`Config::set_max_providers_per_query`].
+    max_providers_per_query: Option<NonZeroUsize>,
+
     local_peer_id: PeerId,
 
     mode: Mode,
@@ -185,6 +188,7 @@ pub struct Config {
     connection_idle_timeout: Duration,
     kbucket_inserts: BucketInserts,
     caching: Caching,
+    max_providers_per_query: Option<NonZeroUsize>,
 }
 
 impl Default for Config {
@@ -202,6 +206,7 @@ impl Default for Config {
             connection_idle_timeout: Duration::from_secs(10),
             kbucket_inserts: BucketInserts::OnConnected,
             caching: Caching::Enabled { max_peers: 1 },
+            max_providers_per_query: None,
         }
     }
 }
@@ -251,11 +256,19 @@ impl Config {
     ///
     /// The replication factor determines to how many closest peers
     /// a record is replicated. The default is [`K_VALUE`].
+    ///
+    /// Note that the capacity of the k-buckets of the routing table is always [`K_VALUE`],
+    /// independent of the replication factor.
     pub fn set_replication_factor(&mut self, replication_factor: NonZeroUsize) -> &mut Self {
         self.query_config.replication_factor = replication_factor;
         self
     }
 
+    /// Returns the replication factor, see [`Config::set_replication_factor`].
+    pub fn replication_factor(&self) -> NonZeroUsize {
+        self.query_config.replication_factor
+    }
+
     /// Sets the allowed level of parallelism for iterative queries.
     ///
     /// The `α` parameter in the Kademlia paper. The maximum number of peers
@@ -402,6 +415,18 @@ impl Config {
         self.caching = c;
         self
     }
+
+    /// Sets the number of providers after which a [`Behaviour::get_providers`]
+    /// query finishes early.
+    ///
+    /// Providers are reported as they are discovered, hence a lookup does not
+    /// need to run to completion if the first few providers are sufficient.
+    ///
+    /// `None` means that queries always run to completion. This is the default.
+    pub fn set_max_providers_per_query(&mut self, max: Option<NonZeroUsize>) -> &mut Self {
+        self.max_providers_per_query = max;
+        self
+    }
 }
 
 impl<TStore> Behaviour<TStore>
@@ -441,6 +466,7 @@ where
         Behaviour {
             store,
             caching: config.caching,
+            max_providers_per_query: config.max_providers_per_query,
             kbuckets: KBucketsTable::new(local_key, config.kbucket_pending_timeout),
             kbucket_inserts: config.kbucket_inserts,
             protocol_config: config.protocol_config,
@@ -642,6 +668,27 @@ where
     }
 
     /// Returns an iterator over all non-empty buckets in the routing table.
+    ///
+    /// This can be used to take a snapshot of the routing table, e.g. when debugging routing
+    /// anomalies:
+    ///
+    /// ```
+    /// # use libp2p_identity::PeerId;
+    /// # use libp2p_kad::{store::MemoryStore, Behaviour, NodeStatus};
+    /// # let local_peer_id = PeerId::random();
+    /// # let mut kademlia = Behaviour::new(local_peer_id, MemoryStore::new(local_peer_id));
+    /// for bucket in kademlia.kbuckets() {
+    ///     let index = bucket.range().1.ilog2();
+    ///
+    ///     for entry in bucket.iter() {
+    ///         let peer_id = entry.node.key.preimage();
+    ///         let addresses = entry.node.value.iter().collect::<Vec<_>>();
+    ///         let connected = entry.status == NodeStatus::Connected;
+    ///
+    ///         println!("{index:?} {peer_id} {addresses:?} connected: {connected}");
+    ///     }
+    /// }
+    /// ```
     pub fn kbuckets(
         &mut self,
     ) -> impl Iterator<Item = kbucket::KBucketRef<'_, kbucket::Key<PeerId>, Addresses>> {
@@ -665,6 +712,39 @@ where
     ///
     /// The result of the query is delivered in a
     /// [`Event::OutboundQueryProgressed{QueryResult::GetClosestPeers}`].
+    ///
+    /// To find a peer in order to connect to it, query for its [`PeerId`] and dial it once the
+    /// query has finished. The addresses learned during the query are used for the dial:
+    ///
+    /// ```
+    /// # use futures::StreamExt;
+    /// # use libp2p_identity::PeerId;
+    /// # use libp2p_kad::{store::MemoryStore, Behaviour, Event, QueryResult};
+    /// # use libp2p_swarm::{Swarm, SwarmEvent};
+    /// async fn connect(swarm: &mut Swarm<Behaviour<MemoryStore>>, target: PeerId) -> bool {
+    ///     let query_id = swarm.behaviour_mut().get_closest_peers(target);
+    ///
+    ///     loop {
+    ///         match swarm.select_next_some().await {
+    ///             SwarmEvent::Behaviour(Event::OutboundQueryProgressed {
+    ///                 id,
+    ///                 result: QueryResult::GetClosestPeers(result),
+    ///                 ..
+    ///             }) if id == query_id => match result {
+    ///                 Ok(ok) if ok.peers.contains(&target) && swarm.dial(target).is_ok() => {}
+    ///                 _ => return false,
+    ///             },
+    ///             SwarmEvent::ConnectionEstablished { peer_id, .. } if peer_id == target => {
+    ///                 return true;
+    ///             }
+    ///             SwarmEvent::OutgoingConnectionError { peer_id, .. } if peer_id == Some(target) => {
+    ///                 return false;
+    ///             }
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    /// ```
     pub fn get_closest_peers<K>(&mut self, key: K) -> QueryId
     where
         K: Into<kbucket::Key<K>> + Into<Vec<u8>> + Clone,
@@ -693,6 +773,15 @@ where
     /// The result of this operation is delivered in a
     /// [`Event::OutboundQueryProgressed{QueryResult::GetRecord}`].
     pub fn get_record(&mut self, key: record_priv::Key) -> QueryId {
+        self.get_record_with_opts(key, QueryOpts::default())
+    }
+
+    /// Performs a lookup for a record in the DHT, like [`Behaviour::get_record`], with
+    /// per-query options.
+    ///
+    /// This allows e.g. a quick best-effort lookup with a short timeout next to lookups using
+    /// the [`Config::set_query_timeout`] of the behaviour.
+    pub fn get_record_with_opts(&mut self, key: record_priv::Key, opts: QueryOpts) -> QueryId {
         let record = if let Some(record) = self.store.get(&key) {
             if record.is_expired(Instant::now()) {
                 self.store.remove(&key);
@@ -727,7 +816,9 @@ where
         };
         let peers = self.kbuckets.closest_keys(&target);
         let inner = QueryInner::new(info);
-        let id = self.queries.add_iter_closest(target.clone(), peers, inner);
+        let id = self
+            .queries
+            .add_iter_closest_with_opts(target.clone(), peers, inner, opts);
 
         // No queries were actually done for the results yet.
         let stats = QueryStats::empty();
@@ -851,6 +942,12 @@ where
     }
 
     /// Gets a mutable reference to the record store.
+    ///
+    /// Records put into the store directly, unlike through [`Behaviour::put_record`], are not
+    /// sent to other peers right away. They are subject to the periodic jobs though: records
+    /// are (re-)replicated every [`Config::set_replication_interval`], and records whose
+    /// publisher is the local peer are (re-)published every [`Config::set_publication_interval`].
+    /// Disable these intervals to keep records strictly local.
     pub fn store_mut(&mut self) -> &mut TStore {
         &mut self.store
     }
@@ -890,6 +987,24 @@ where
         }
     }
 
+    /// Adds the given peers and addresses to the routing table and bootstraps the local node.
+    ///
+    /// This is a shorthand for calling [`Behaviour::add_address`] for every entry, followed by
+    /// [`Behaviour::bootstrap`], e.g. to join the DHT through a list of well-known bootstrap
+    /// nodes.
+    ///
+    /// Returns `Err` if bootstrapping is impossible due to an empty routing table, i.e. if no
+    /// peers were given and none were known before.
+    pub fn bootstrap_with_peers<I>(&mut self, peers: I) -> Result<QueryId, NoKnownPeers>
+    where
+        I: IntoIterator<Item = (PeerId, Multiaddr)>,
+    {
+        for (peer, address) in peers {
+            self.add_address(&peer, address);
+        }
+        self.bootstrap()
+    }
+
     /// Establishes the local node as a provider of a value for the given key.
     ///
     /// This operation publishes a provider record with the given key and
@@ -949,6 +1064,11 @@ where
     ///
     /// The result of this operation is delivered in a
     /// reported via [`Event::OutboundQueryProgressed{QueryResult::GetProviders}`].
+    ///
+    /// Providers are reported in [`GetProvidersOk::FoundProviders`] as soon as they are
+    /// discovered, followed by a final [`GetProvidersOk::FinishedWithNoAdditionalRecord`].
+    /// The query can be stopped early via [`QueryMut::finish`] or by configuring
+    /// [`Config::set_max_providers_per_query`].
     pub fn get_providers(&mut self, key: record_priv::Key) -> QueryId {
         let providers: HashSet<_> = self
             .store
@@ -975,6 +1095,15 @@ where
         let inner = QueryInner::new(info);
         let id = self.queries.add_iter_closest(target.clone(), peers, inner);
 
+        if self
+            .max_providers_per_query
+            .map_or(false, |max| providers.len() >= max.get())
+        {
+            if let Some(query) = self.queries.get_mut(&id) {
+                query.finish();
+            }
+        }
+
         // No queries were actually done for the results yet.
         let stats = QueryStats::empty();
 
@@ -2249,6 +2378,13 @@ where
                             },
                         ));
                         *step = step.next();
+
+                        if self
+                            .max_providers_per_query
+                            .map_or(false, |max| *providers_found >= max.get())
+                        {
+                            query.finish();
+                        }
                     }
                 }
             }
@@ -238,6 +238,60 @@ fn bootstrap() {
     QuickCheck::new().tests(10).quickcheck(prop as fn(_) -> _)
 }
 
+#[test]
+fn bootstrap_with_peers() {
+    let mut swarms = build_nodes(3);
+    let (_, mut swarm) = swarms.remove(0);
+    let bootnodes = swarms
+        .iter()
+        .map(|(addr, swarm)| (*swarm.local_peer_id(), addr.clone()))
+        .collect::<Vec<_>>();
+    let expected_known = bootnodes
+        .iter()
+        .map(|(peer, _)| *peer)
+        .collect::<HashSet<_>>();
+
+    assert!(swarm.behaviour_mut().bootstrap_with_peers(vec![]).is_err());
+    let qid = swarm
+        .behaviour_mut()
+        .bootstrap_with_peers(bootnodes)
+        .unwrap();
+
+    let mut swarms = swarms.into_iter().map(|(_, s)| s).collect::<Vec<_>>();
+    swarms.insert(0, swarm);
+    block_on(poll_fn(move |ctx| {
+        for (i, swarm) in swarms.iter_mut().enumerate() {
+            loop {
+                match swarm.poll_next_unpin(ctx) {
+                    Poll::Ready(Some(SwarmEvent::Behaviour(Event::OutboundQueryProgressed {
+                        id,
+                        result: QueryResult::Bootstrap(Ok(ok)),
+                        ..
+                    }))) => {
+                        assert_eq!(id, qid);
+                        assert_eq!(i, 0);
+                        if ok.num_remaining == 0 {
+                            let mut known = HashSet::new();
+                            for b in swarm.behaviour_mut().kbuckets.iter() {
+                                for e in b.iter() {
+                                    known.insert(*e.node.key.preimage());
+                                }
+                            }
+                            assert_eq!(expected_known, known);
+                            return Poll::Ready(());
+                        }
+                    }
+                    // Ignore any other event.
+                    Poll::Ready(Some(_)) => (),
+                    e @ Poll::Ready(_) => panic!("Unexpected return value: {e:?}"),
+                    Poll::Pending => break,
+                }
+            }
+        }
+        Poll::Pending
+    }))
+}
+
 #[test]
 fn query_iter() {
     fn distances<K>(key: &kbucket::Key<K>, peers: Vec<PeerId>) -> Vec<Distance> {
@@ -1521,3 +1575,64 @@ fn get_providers_limit_n_2() {
 fn get_providers_limit_n_5() {
     get_providers_limit::<5>();
 }
+
+#[test]
+fn get_providers_finishes_after_max_providers() {
+    let mut cfg = Config::default();
+    cfg.set_max_providers_per_query(Some(NonZeroUsize::new(1).unwrap()));
+    let mut swarms = build_nodes_with_config(3, cfg);
+
+    // Let first peer know of second peer and second peer know of third peer.
+    for i in 0..2 {
+        let (peer_id, address) = (
+            *Swarm::local_peer_id(&swarms[i + 1].1),
+            swarms[i + 1].0.clone(),
+        );
+        swarms[i].1.behaviour_mut().add_address(&peer_id, address);
+    }
+
+    let mut swarms = swarms
+        .into_iter()
+        .map(|(_addr, swarm)| swarm)
+        .collect::<Vec<_>>();
+
+    // Provide the content on peer 2 and 3.
+    let key = Key::new(&random::<[u8; 32]>().to_vec());
+    for swarm in swarms.iter_mut().skip(1) {
+        swarm
+            .behaviour_mut()
+            .start_providing(key.clone())
+            .expect("could not provide");
+    }
+
+    let query_id = swarms[0].behaviour_mut().get_providers(key);
+    let mut all_providers: Vec<PeerId> = vec![];
+
+    block_on(poll_fn(move |ctx| {
+        for (i, swarm) in swarms.iter_mut().enumerate() {
+            loop {
+                match swarm.poll_next_unpin(ctx) {
+                    Poll::Ready(Some(SwarmEvent::Behaviour(Event::OutboundQueryProgressed {
+                        id,
+                        result: QueryResult::GetProviders(Ok(ok)),
+                        step,
+                        ..
+                    }))) if i == 0 && id == query_id => match ok {
+                        GetProvidersOk::FoundProviders { providers, .. } => {
+                            all_providers.extend(providers);
+                        }
+                        GetProvidersOk::FinishedWithNoAdditionalRecord { .. } => {
+                            assert!(step.last);
+                            // The query must not continue to the third peer.
+                            assert_eq!(all_providers.len(), 1);
+                            return Poll::Ready(());
+                        }
+                    },
+                    Poll::Ready(..) => {}
+                    Poll::Pending => break,
+                }
+            }
+        }
+        Poll::Pending
+    }));
+}
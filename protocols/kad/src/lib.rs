@@ -72,9 +72,11 @@ pub use behaviour::{
 pub use behaviour::{
     Behaviour, BucketInserts, Caching, Config, Event, ProgressStep, Quorum, StoreInserts,
 };
-pub use kbucket::{Distance as KBucketDistance, EntryView, KBucketRef, Key as KBucketKey};
+pub use kbucket::{
+    Distance as KBucketDistance, EntryView, KBucketRef, Key as KBucketKey, NodeStatus,
+};
 pub use protocol::ConnectionType;
-pub use query::QueryId;
+pub use query::{QueryId, QueryOpts};
 pub use record_priv::{store, Key as RecordKey, ProviderRecord, Record};
 
 use libp2p_swarm::StreamProtocol;
@@ -114,12 +114,28 @@ impl<TInner> QueryPool<TInner> {
 
     /// Adds a query to the pool that iterates towards the closest peers to the target.
     pub(crate) fn add_iter_closest<T, I>(&mut self, target: T, peers: I, inner: TInner) -> QueryId
+    where
+        T: Into<KeyBytes> + Clone,
+        I: IntoIterator<Item = Key<PeerId>>,
+    {
+        self.add_iter_closest_with_opts(target, peers, inner, QueryOpts::default())
+    }
+
+    /// Adds a query to the pool that iterates towards the closest peers to the target,
+    /// overriding parts of the pool's [`QueryConfig`] for this query.
+    pub(crate) fn add_iter_closest_with_opts<T, I>(
+        &mut self,
+        target: T,
+        peers: I,
+        inner: TInner,
+        opts: QueryOpts,
+    ) -> QueryId
     where
         T: Into<KeyBytes> + Clone,
         I: IntoIterator<Item = Key<PeerId>>,
     {
         let id = self.next_query_id();
-        self.continue_iter_closest(id, target, peers, inner);
+        self.continue_iter_closest_with_opts(id, target, peers, inner, opts);
         id
     }
 
@@ -133,10 +149,24 @@ impl<TInner> QueryPool<TInner> {
     ) where
         T: Into<KeyBytes> + Clone,
         I: IntoIterator<Item = Key<PeerId>>,
+    {
+        self.continue_iter_closest_with_opts(id, target, peers, inner, QueryOpts::default())
+    }
+
+    fn continue_iter_closest_with_opts<T, I>(
+        &mut self,
+        id: QueryId,
+        target: T,
+        peers: I,
+        inner: TInner,
+        opts: QueryOpts,
+    ) where
+        T: Into<KeyBytes> + Clone,
+        I: IntoIterator<Item = Key<PeerId>>,
     {
         let cfg = ClosestPeersIterConfig {
             num_results: self.config.replication_factor,
-            parallelism: self.config.parallelism,
+            parallelism: opts.parallelism.unwrap_or(self.config.parallelism),
             ..ClosestPeersIterConfig::default()
         };
 
@@ -148,7 +178,8 @@ impl<TInner> QueryPool<TInner> {
             QueryPeerIter::Closest(ClosestPeersIter::with_config(cfg, target, peers))
         };
 
-        let query = Query::new(id, peer_iter, inner);
+        let mut query = Query::new(id, peer_iter, inner);
+        query.timeout = opts.timeout;
         self.queries.insert(id, query);
     }
 
@@ -188,7 +219,7 @@ impl<TInner> QueryPool<TInner> {
                 }
                 PeersIterState::Waiting(None) | PeersIterState::WaitingAtCapacity => {
                     let elapsed = now - query.stats.start.unwrap_or(now);
-                    if elapsed >= self.config.timeout {
+                    if elapsed >= query.timeout.unwrap_or(self.config.timeout) {
                         timeout = Some(query_id);
                         break;
                     }
@@ -225,6 +256,31 @@ impl<TInner> QueryPool<TInner> {
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub struct QueryId(usize);
 
+/// Options of a single query, overriding the ones of the [`Config`](crate::Config).
+///
+/// See [`Behaviour::get_record_with_opts`](crate::Behaviour::get_record_with_opts).
+#[derive(Debug, Clone, Default)]
+pub struct QueryOpts {
+    timeout: Option<Duration>,
+    parallelism: Option<NonZeroUsize>,
+}
+
+impl QueryOpts {
+    /// Sets the timeout of the query, overriding
+    /// [`Config::set_query_timeout`](crate::Config::set_query_timeout).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of peers the query contacts in parallel (alpha in the paper),
+    /// overriding [`Config::set_parallelism`](crate::Config::set_parallelism).
+    pub fn with_parallelism(mut self, parallelism: NonZeroUsize) -> Self {
+        self.parallelism = Some(parallelism);
+        self
+    }
+}
+
 /// The configuration for queries in a `QueryPool`.
 #[derive(Debug, Clone)]
 pub(crate) struct QueryConfig {
@@ -265,6 +321,8 @@ pub(crate) struct Query<TInner> {
     peer_iter: QueryPeerIter,
     /// Execution statistics of the query.
     stats: QueryStats,
+    /// Overrides [`QueryConfig::timeout`] for this query.
+    timeout: Option<Duration>,
     /// The opaque inner query state.
     pub(crate) inner: TInner,
 }
@@ -284,6 +342,7 @@ impl<TInner> Query<TInner> {
             inner,
             peer_iter,
             stats: QueryStats::empty(),
+            timeout: None,
         }
     }
 
@@ -506,3 +565,32 @@ impl QueryStats {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_timeout_can_be_overridden() {
+        let mut pool = QueryPool::new(QueryConfig::default());
+        let target = Key::from(PeerId::random());
+        let peers = || std::iter::once(Key::from(PeerId::random()));
+
+        let default_id = pool.add_iter_closest(target.clone(), peers(), ());
+        let short_id = pool.add_iter_closest_with_opts(
+            target,
+            peers(),
+            (),
+            QueryOpts::default().with_timeout(Duration::from_secs(1)),
+        );
+
+        let now = Instant::now();
+        while let QueryPoolState::Waiting(Some(_)) = pool.poll(now) {}
+
+        match pool.poll(now + Duration::from_secs(2)) {
+            QueryPoolState::Timeout(query) => assert_eq!(query.id(), short_id),
+            _ => panic!("Expected the query with the short timeout to time out"),
+        }
+        assert!(pool.get(&default_id).is_some());
+    }
+}
@@ -63,6 +63,10 @@ pub const IPV6_MDNS_MULTICAST_ADDRESS: Ipv6Addr = Ipv6Addr::new(0xFF02, 0, 0, 0,
 #[derive(Debug, Clone)]
 pub struct Config {
     /// TTL to use for mdns records.
+    ///
+    /// Remote peers cache our addresses for this long. If we stop responding to their queries,
+    /// they emit [`Event::Expired`] for us once the TTL has elapsed. Likewise, discovered peers
+    /// expire based on the TTL of the records they announced.
     pub ttl: Duration,
     /// Interval at which to poll the network for new peers. This isn't
     /// necessary during normal operation but avoids the case that an
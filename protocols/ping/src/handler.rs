@@ -47,6 +47,8 @@ pub struct Config {
     timeout: Duration,
     /// The duration between outbound pings.
     interval: Duration,
+    /// The fraction of the interval by which outbound pings are randomly delayed.
+    interval_jitter: f64,
 }
 
 impl Config {
@@ -54,6 +56,7 @@ impl Config {
     ///
     ///   * [`Config::with_interval`] 15s
     ///   * [`Config::with_timeout`] 20s
+    ///   * [`Config::with_interval_jitter`] 0.0
     ///
     /// These settings have the following effect:
     ///
@@ -64,6 +67,7 @@ impl Config {
         Self {
             timeout: Duration::from_secs(20),
             interval: Duration::from_secs(15),
+            interval_jitter: 0.0,
         }
     }
 
@@ -78,6 +82,30 @@ impl Config {
         self.interval = d;
         self
     }
+
+    /// Sets the ping interval jitter.
+    ///
+    /// Every outbound ping is delayed by an additional random duration of up to
+    /// `interval * jitter`, chosen independently per connection and ping. This avoids the pings
+    /// of many connections established at the same time being sent in bursts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `jitter` is not within `0.0..=1.0`.
+    pub fn with_interval_jitter(mut self, jitter: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&jitter),
+            "ping interval jitter must be within 0.0..=1.0"
+        );
+        self.interval_jitter = jitter;
+        self
+    }
+
+    /// Returns the duration until the next outbound ping, including the jitter.
+    fn jittered_interval(&self) -> Duration {
+        self.interval
+            .mul_f64(1.0 + self.interval_jitter * rand::random::<f64>())
+    }
 }
 
 impl Default for Config {
@@ -298,12 +326,12 @@ impl ConnectionHandler for Handler {
                         log::debug!("latency to {} is {}ms", self.peer, rtt.as_millis());
 
                         self.failures = 0;
-                        self.interval.reset(self.config.interval);
+                        self.interval.reset(self.config.jittered_interval());
                         self.outbound = Some(OutboundState::Idle(stream));
                         return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(Ok(rtt)));
                     }
                     Poll::Ready(Err(e)) => {
-                        self.interval.reset(self.config.interval);
+                        self.interval.reset(self.config.jittered_interval());
                         self.pending_errors.push_front(e);
                     }
                 },
@@ -397,3 +425,22 @@ async fn send_ping(stream: Stream, timeout: Duration) -> Result<(Stream, Duratio
         Either::Right(((), _)) => Err(Failure::Timeout),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jittered_interval_stays_within_bounds() {
+        let config = Config::new()
+            .with_interval(Duration::from_secs(10))
+            .with_interval_jitter(0.5);
+
+        for _ in 0..100 {
+            let interval = config.jittered_interval();
+            assert!(interval >= Duration::from_secs(10));
+            assert!(interval <= Duration::from_secs(15));
+        }
+        assert_eq!(Config::new().jittered_interval(), Duration::from_secs(15));
+    }
+}
@@ -27,6 +27,9 @@ use crate::multiaddr_ext::MultiaddrExt;
 use crate::proto;
 use crate::protocol::{inbound_hop, outbound_stop};
 use either::Either;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use futures::FutureExt;
 use instant::Instant;
 use libp2p_core::multiaddr::Protocol;
 use libp2p_core::{ConnectedPoint, Endpoint, Multiaddr};
@@ -232,6 +235,26 @@ pub struct Behaviour {
     queued_actions: VecDeque<ToSwarm<Event, THandlerInEvent<Self>>>,
 
     external_addresses: ExternalAddresses,
+
+    /// Decides whether a peer may make a reservation.
+    reservation_handler: Option<ReservationHandler>,
+    /// Reservation requests waiting for the decision of the `reservation_handler`.
+    pending_reservations: FuturesUnordered<BoxFuture<'static, PendingReservation>>,
+    /// Number of `pending_reservations` per peer and connection.
+    ///
+    /// Entries are removed once their connection closes, whereupon the decisions of the
+    /// corresponding `pending_reservations` are discarded.
+    num_pending_reservations: HashMap<PeerId, HashMap<ConnectionId, usize>>,
+}
+
+type ReservationHandler = Box<dyn Fn(PeerId) -> BoxFuture<'static, bool> + Send>;
+
+/// A reservation request together with the decision of the [`ReservationHandler`].
+struct PendingReservation {
+    peer_id: PeerId,
+    connection: ConnectionId,
+    inbound_reservation_req: inbound_hop::ReservationReq,
+    allowed: bool,
 }
 
 impl Behaviour {
@@ -243,9 +266,80 @@ impl Behaviour {
             circuits: Default::default(),
             queued_actions: Default::default(),
             external_addresses: Default::default(),
+            reservation_handler: None,
+            pending_reservations: Default::default(),
+            num_pending_reservations: Default::default(),
+        }
+    }
+
+    /// Only accept reservations of peers approved by the given handler.
+    ///
+    /// The handler is called for every reservation request, including renewals, that is within
+    /// the limits of the [`Config`]. A reservation is only accepted if the returned future
+    /// resolves to `true`, otherwise it is denied with [`Status::PERMISSION_DENIED`]. This allows
+    /// e.g. private relays to check peers against a membership list.
+    ///
+    /// [`Status::PERMISSION_DENIED`]: proto::Status::PERMISSION_DENIED
+    pub fn with_reservation_handler(
+        mut self,
+        handler: impl Fn(PeerId) -> BoxFuture<'static, bool> + Send + 'static,
+    ) -> Self {
+        self.reservation_handler = Some(Box::new(handler));
+        self
+    }
+
+    fn accept_reservation(
+        &mut self,
+        peer_id: PeerId,
+        connection: ConnectionId,
+        inbound_reservation_req: inbound_hop::ReservationReq,
+    ) -> ToSwarm<Event, THandlerInEvent<Self>> {
+        self.reservations
+            .entry(peer_id)
+            .or_default()
+            .insert(connection);
+
+        ToSwarm::NotifyHandler {
+            handler: NotifyHandler::One(connection),
+            peer_id,
+            event: Either::Left(handler::In::AcceptReservationReq {
+                inbound_reservation_req,
+                addrs: self
+                    .external_addresses
+                    .iter()
+                    .cloned()
+                    // Add local peer ID in case it isn't present yet.
+                    .filter_map(|a| match a.iter().last()? {
+                        Protocol::P2p(_) => Some(a),
+                        _ => Some(a.with(Protocol::P2p(self.local_peer_id))),
+                    })
+                    .collect(),
+            }),
         }
     }
 
+    /// Removes a decided reservation request from `num_pending_reservations`.
+    ///
+    /// Returns `false` if the connection of the request has been closed in the meantime.
+    fn remove_pending_reservation(&mut self, peer_id: PeerId, connection: ConnectionId) -> bool {
+        let Some(connections) = self.num_pending_reservations.get_mut(&peer_id) else {
+            return false;
+        };
+        let Some(num_pending) = connections.get_mut(&connection) else {
+            return false;
+        };
+
+        *num_pending -= 1;
+        if *num_pending == 0 {
+            connections.remove(&connection);
+        }
+        if connections.is_empty() {
+            self.num_pending_reservations.remove(&peer_id);
+        }
+
+        true
+    }
+
     fn on_connection_closed(
         &mut self,
         ConnectionClosed {
@@ -261,6 +355,13 @@ impl Behaviour {
             }
         }
 
+        if let hash_map::Entry::Occupied(mut peer) = self.num_pending_reservations.entry(peer_id) {
+            peer.get_mut().remove(&connection_id);
+            if peer.get().is_empty() {
+                peer.remove();
+            }
+        }
+
         for circuit in self
             .circuits
             .remove_by_connection(peer_id, connection_id)
@@ -387,6 +488,11 @@ impl NetworkBehaviour for Behaviour {
                         .get(&event_source)
                         .map(|cs| cs.len())
                         .unwrap_or(0)
+                        + self
+                            .num_pending_reservations
+                            .get(&event_source)
+                            .map(|cs| cs.values().sum())
+                            .unwrap_or(0)
                         > self.config.max_reservations_per_peer)
                     // Deny if it exceeds `max_reservations`.
                     || self
@@ -394,6 +500,11 @@ impl NetworkBehaviour for Behaviour {
                         .values()
                         .map(|cs| cs.len())
                         .sum::<usize>()
+                        + self
+                            .num_pending_reservations
+                            .values()
+                            .flat_map(|cs| cs.values())
+                            .sum::<usize>()
                         >= self.config.max_reservations
                     // Deny if it exceeds the allowed rate of reservations.
                     || !self
@@ -411,30 +522,30 @@ impl NetworkBehaviour for Behaviour {
                             status: proto::Status::RESOURCE_LIMIT_EXCEEDED,
                         }),
                     }
-                } else {
-                    // Accept reservation.
-                    self.reservations
+                } else if let Some(reservation_handler) = &self.reservation_handler {
+                    // Ask the handler whether to accept the reservation.
+                    let allowed = reservation_handler(event_source);
+                    *self
+                        .num_pending_reservations
                         .entry(event_source)
                         .or_default()
-                        .insert(connection);
-
-                    ToSwarm::NotifyHandler {
-                        handler: NotifyHandler::One(connection),
-                        peer_id: event_source,
-                        event: Either::Left(handler::In::AcceptReservationReq {
-                            inbound_reservation_req,
-                            addrs: self
-                                .external_addresses
-                                .iter()
-                                .cloned()
-                                // Add local peer ID in case it isn't present yet.
-                                .filter_map(|a| match a.iter().last()? {
-                                    Protocol::P2p(_) => Some(a),
-                                    _ => Some(a.with(Protocol::P2p(self.local_peer_id))),
-                                })
-                                .collect(),
-                        }),
-                    }
+                        .entry(connection)
+                        .or_default() += 1;
+                    self.pending_reservations.push(
+                        async move {
+                            PendingReservation {
+                                peer_id: event_source,
+                                connection,
+                                inbound_reservation_req,
+                                allowed: allowed.await,
+                            }
+                        }
+                        .boxed(),
+                    );
+                    return;
+                } else {
+                    // Accept reservation.
+                    self.accept_reservation(event_source, connection, inbound_reservation_req)
                 };
 
                 self.queued_actions.push_back(action);
@@ -691,13 +802,43 @@ impl NetworkBehaviour for Behaviour {
 
     fn poll(
         &mut self,
-        _cx: &mut Context<'_>,
+        cx: &mut Context<'_>,
         _: &mut impl PollParameters,
     ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
         if let Some(to_swarm) = self.queued_actions.pop_front() {
             return Poll::Ready(to_swarm);
         }
 
+        while let Poll::Ready(Some(PendingReservation {
+            peer_id,
+            connection,
+            inbound_reservation_req,
+            allowed,
+        })) = self.pending_reservations.poll_next_unpin(cx)
+        {
+            if !self.remove_pending_reservation(peer_id, connection) {
+                // The connection closed in the meantime.
+                continue;
+            }
+
+            if allowed {
+                return Poll::Ready(self.accept_reservation(
+                    peer_id,
+                    connection,
+                    inbound_reservation_req,
+                ));
+            }
+
+            return Poll::Ready(ToSwarm::NotifyHandler {
+                handler: NotifyHandler::One(connection),
+                peer_id,
+                event: Either::Left(handler::In::DenyReservationReq {
+                    inbound_reservation_req,
+                    status: proto::Status::PERMISSION_DENIED,
+                }),
+            });
+        }
+
         Poll::Pending
     }
 }
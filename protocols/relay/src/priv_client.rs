@@ -105,6 +105,13 @@ pub struct Behaviour {
 }
 
 /// Create a new client relay [`Behaviour`] with it's corresponding [`Transport`].
+///
+/// The [`Transport`] dials and listens on `/p2p-circuit` addresses, but it only works together
+/// with the returned [`Behaviour`], which opens the circuits on the connections to the relays.
+/// Both thus have to be part of the same `Swarm`. Most users should let
+/// `libp2p::SwarmBuilder::with_relay_client` take care of this, which also upgrades the relayed
+/// connections with the given security and multiplexer protocols and hands the [`Behaviour`] to
+/// `with_behaviour`.
 pub fn new(local_peer_id: PeerId) -> (Transport, Behaviour) {
     let (transport, from_transport) = Transport::new();
     let behaviour = Behaviour {
@@ -18,6 +18,7 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use futures::channel::{mpsc, oneshot};
 use futures::executor::LocalPool;
 use futures::future::FutureExt;
 use futures::io::{AsyncRead, AsyncWrite};
@@ -168,6 +169,196 @@ fn new_reservation_to_same_relay_replaces_old() {
     });
 }
 
+#[test]
+fn reservation_handler_decides_on_reservations() {
+    let _ = env_logger::try_init();
+    let mut pool = LocalPool::new();
+
+    let mut allowed_client = build_client();
+    let allowed_client_peer_id = *allowed_client.local_peer_id();
+    let mut denied_client = build_client();
+
+    let relay_addr = Multiaddr::empty().with(Protocol::Memory(rand::random::<u64>()));
+    let mut relay = build_relay_with(
+        relay::Config {
+            reservation_duration: Duration::from_secs(2),
+            ..Default::default()
+        },
+        |relay| {
+            relay.with_reservation_handler(move |peer_id| {
+                async move { peer_id == allowed_client_peer_id }.boxed()
+            })
+        },
+    );
+    let relay_peer_id = *relay.local_peer_id();
+
+    relay.listen_on(relay_addr.clone()).unwrap();
+    relay.add_external_address(relay_addr.clone());
+    spawn_swarm_on_pool(&pool, relay);
+
+    let client_addr = relay_addr
+        .with(Protocol::P2p(relay_peer_id))
+        .with(Protocol::P2pCircuit);
+
+    allowed_client.listen_on(client_addr.clone()).unwrap();
+    assert!(pool.run_until(wait_for_dial(&mut allowed_client, relay_peer_id)));
+    pool.run_until(wait_for_reservation(
+        &mut allowed_client,
+        client_addr
+            .clone()
+            .with(Protocol::P2p(allowed_client_peer_id)),
+        relay_peer_id,
+        false, // No renewal.
+    ));
+
+    denied_client.listen_on(client_addr).unwrap();
+    assert!(pool.run_until(wait_for_dial(&mut denied_client, relay_peer_id)));
+    pool.run_until(async {
+        loop {
+            match denied_client.select_next_some().await {
+                // The client closes the connection when its reservation is denied.
+                SwarmEvent::ConnectionClosed {
+                    peer_id,
+                    cause: Some(cause),
+                    ..
+                } if peer_id == relay_peer_id => {
+                    assert!(format!("{cause:?}").contains("PERMISSION_DENIED"));
+                    break;
+                }
+                SwarmEvent::ListenerClosed { .. } => {}
+                SwarmEvent::Behaviour(ClientEvent::Ping(_)) => {}
+                e => panic!("{e:?}"),
+            }
+        }
+    });
+}
+
+#[test]
+fn pending_reservations_count_against_limits() {
+    let _ = env_logger::try_init();
+    let mut pool = LocalPool::new();
+
+    let (mut relay, mut reservation_requests) = build_relay_with_manual_approval();
+    let relay_peer_id = *relay.local_peer_id();
+    let relay_addr = Multiaddr::empty().with(Protocol::Memory(rand::random::<u64>()));
+    relay.listen_on(relay_addr.clone()).unwrap();
+    relay.add_external_address(relay_addr.clone());
+
+    let client_addr = relay_addr
+        .with(Protocol::P2p(relay_peer_id))
+        .with(Protocol::P2pCircuit);
+    let mut pending_client = build_client();
+    let mut denied_client = build_client();
+
+    pool.run_until(async {
+        pending_client.listen_on(client_addr.clone()).unwrap();
+        let approval = loop {
+            futures::select! {
+                _ = relay.select_next_some() => {}
+                _ = pending_client.select_next_some() => {}
+                request = reservation_requests.select_next_some() => break request.1,
+            }
+        };
+
+        // The pending reservation occupies the only slot.
+        denied_client.listen_on(client_addr.clone()).unwrap();
+        loop {
+            futures::select! {
+                _ = relay.select_next_some() => {}
+                _ = pending_client.select_next_some() => {}
+                event = denied_client.select_next_some() => {
+                    if let SwarmEvent::Behaviour(ClientEvent::Relay(
+                        relay::client::Event::ReservationReqFailed { error, .. },
+                    )) = event
+                    {
+                        assert!(format!("{error:?}").contains("ResourceLimitExceeded"));
+                        break;
+                    }
+                }
+                _ = reservation_requests.select_next_some() => {
+                    panic!("Expect reservation to be denied before asking the handler")
+                }
+            }
+        }
+
+        approval.send(true).unwrap();
+        loop {
+            futures::select! {
+                _ = relay.select_next_some() => {}
+                _ = denied_client.select_next_some() => {}
+                event = pending_client.select_next_some() => {
+                    if let SwarmEvent::Behaviour(ClientEvent::Relay(
+                        relay::client::Event::ReservationReqAccepted { .. },
+                    )) = event
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn reservation_approved_after_connection_closed_is_discarded() {
+    let _ = env_logger::try_init();
+    let mut pool = LocalPool::new();
+
+    let (mut relay, mut reservation_requests) = build_relay_with_manual_approval();
+    let relay_peer_id = *relay.local_peer_id();
+    let relay_addr = Multiaddr::empty().with(Protocol::Memory(rand::random::<u64>()));
+    relay.listen_on(relay_addr.clone()).unwrap();
+    relay.add_external_address(relay_addr.clone());
+
+    let client_addr = relay_addr
+        .with(Protocol::P2p(relay_peer_id))
+        .with(Protocol::P2pCircuit);
+    let mut closed_client = build_client();
+    let closed_client_peer_id = *closed_client.local_peer_id();
+    let mut client = build_client();
+
+    pool.run_until(async {
+        closed_client.listen_on(client_addr.clone()).unwrap();
+        let approval = loop {
+            futures::select! {
+                _ = relay.select_next_some() => {}
+                _ = closed_client.select_next_some() => {}
+                request = reservation_requests.select_next_some() => break request.1,
+            }
+        };
+
+        drop(closed_client);
+        loop {
+            if let SwarmEvent::ConnectionClosed { peer_id, .. } = relay.select_next_some().await {
+                if peer_id == closed_client_peer_id {
+                    break;
+                }
+            }
+        }
+
+        // Approving the reservation of the closed connection must not occupy the only slot.
+        approval.send(true).unwrap();
+        client.listen_on(client_addr.clone()).unwrap();
+        loop {
+            futures::select! {
+                _ = relay.select_next_some() => {}
+                request = reservation_requests.select_next_some() => {
+                    request.1.send(true).unwrap();
+                }
+                event = client.select_next_some() => match event {
+                    SwarmEvent::Behaviour(ClientEvent::Relay(
+                        relay::client::Event::ReservationReqAccepted { .. },
+                    )) => break,
+                    SwarmEvent::Behaviour(ClientEvent::Relay(
+                        relay::client::Event::ReservationReqFailed { error, .. },
+                    )) => panic!("Expect reservation to be accepted: {error:?}"),
+                    _ => {}
+                },
+            }
+        }
+    });
+}
+
 #[test]
 fn connect() {
     let _ = env_logger::try_init();
@@ -305,6 +496,19 @@ fn reuse_connection() {
 }
 
 fn build_relay() -> Swarm<Relay> {
+    build_relay_with(
+        relay::Config {
+            reservation_duration: Duration::from_secs(2),
+            ..Default::default()
+        },
+        |relay| relay,
+    )
+}
+
+fn build_relay_with(
+    config: relay::Config,
+    f: impl FnOnce(relay::Behaviour) -> relay::Behaviour,
+) -> Swarm<Relay> {
     let local_key = identity::Keypair::generate_ed25519();
     let local_peer_id = local_key.public().to_peer_id();
 
@@ -314,19 +518,38 @@ fn build_relay() -> Swarm<Relay> {
         transport,
         Relay {
             ping: ping::Behaviour::new(ping::Config::new()),
-            relay: relay::Behaviour::new(
-                local_peer_id,
-                relay::Config {
-                    reservation_duration: Duration::from_secs(2),
-                    ..Default::default()
-                },
-            ),
+            relay: f(relay::Behaviour::new(local_peer_id, config)),
         },
         local_peer_id,
         Config::with_async_std_executor(),
     )
 }
 
+/// Builds a relay with a single reservation slot whose reservation requests are approved through
+/// the returned channel.
+fn build_relay_with_manual_approval() -> (
+    Swarm<Relay>,
+    mpsc::Receiver<(PeerId, oneshot::Sender<bool>)>,
+) {
+    let (request_tx, request_rx) = mpsc::channel(0);
+    let relay = build_relay_with(
+        relay::Config {
+            max_reservations: 1,
+            ..Default::default()
+        },
+        |relay| {
+            relay.with_reservation_handler(move |peer_id| {
+                let (tx, rx) = oneshot::channel();
+                // Every sender has a guaranteed slot, hence sending on a fresh clone always succeeds.
+                request_tx.clone().try_send((peer_id, tx)).unwrap();
+                rx.map(|allowed| allowed.unwrap_or(false)).boxed()
+            })
+        },
+    );
+
+    (relay, request_rx)
+}
+
 fn build_client() -> Swarm<Client> {
     let local_key = identity::Keypair::generate_ed25519();
     let local_peer_id = local_key.public().to_peer_id();
@@ -85,6 +85,13 @@ impl Behaviour {
     }
 }
 
+/// Events emitted by the rendezvous server [`Behaviour`].
+///
+/// Clients learn about registrations by polling the server with discover requests. To notify
+/// interested nodes as soon as a peer (un)registers, compose this behaviour with a pubsub protocol
+/// such as `libp2p-gossipsub` using `#[derive(NetworkBehaviour)]` and publish
+/// [`Event::PeerRegistered`], [`Event::PeerUnregistered`] and [`Event::RegistrationExpired`] on a
+/// topic per [`Namespace`], e.g. `rendezvous/<namespace>`.
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum Event {
@@ -25,6 +25,12 @@ use std::io;
 /// A `Codec` defines the request and response types
 /// for a request-response [`Behaviour`](crate::Behaviour) protocol or
 /// protocol family and how they are encoded / decoded on an I/O stream.
+///
+/// Responses are written by value, so large payloads don't need to be held in memory: a
+/// `Response` type carrying an [`AsyncRead`] can be copied to the stream in
+/// [`Codec::write_response`], e.g. via [`futures::io::copy`]. Note that the whole exchange,
+/// including writing the response, is subject to the configured
+/// [`Config::set_request_timeout`](crate::Config::set_request_timeout).
 #[async_trait]
 pub trait Codec {
     /// The type of protocol(s) or protocol versions being negotiated.
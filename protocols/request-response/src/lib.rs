@@ -54,6 +54,14 @@
 //! For that purpose, [`Codec::Protocol`] is typically
 //! instantiated with a sum type.
 //!
+//! Since every [`Codec`] method is given the protocol negotiated on the
+//! substream, this also covers supporting multiple wire formats for the
+//! same messages, e.g. `/my-app/1.0.0/proto` and `/my-app/1.0.0/json`:
+//! the codec picks the encoding based on the protocol it is called with.
+//! For outbound requests, the protocols are proposed to the remote in the
+//! order they were passed to [`Behaviour::new`], so list the preferred
+//! format first.
+//!
 //! ## Limited Protocol Support
 //!
 //! It is possible to only support inbound or outbound requests for
@@ -91,6 +99,7 @@ use smallvec::SmallVec;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     fmt,
+    num::NonZeroUsize,
     sync::{atomic::AtomicU64, Arc},
     task::{Context, Poll},
     time::Duration,
@@ -285,6 +294,7 @@ impl fmt::Display for RequestId {
 pub struct Config {
     request_timeout: Duration,
     connection_keep_alive: Duration,
+    max_concurrent_outbound_streams_per_peer: usize,
 }
 
 impl Default for Config {
@@ -292,6 +302,7 @@ impl Default for Config {
         Self {
             connection_keep_alive: Duration::from_secs(10),
             request_timeout: Duration::from_secs(10),
+            max_concurrent_outbound_streams_per_peer: usize::MAX,
         }
     }
 }
@@ -308,6 +319,18 @@ impl Config {
         self.request_timeout = v;
         self
     }
+
+    /// Sets the maximum number of outbound requests in flight to a single peer, i.e. the
+    /// maximum number of streams opened concurrently for outbound requests to it.
+    ///
+    /// Further requests to the peer are queued and sent as earlier ones complete.
+    /// See [`Behaviour::num_queued_outbound_requests`].
+    ///
+    /// Defaults to no limit.
+    pub fn set_max_concurrent_outbound_streams_per_peer(&mut self, v: NonZeroUsize) -> &mut Self {
+        self.max_concurrent_outbound_streams_per_peer = v.get();
+        self
+    }
 }
 
 /// A request/response protocol for some message codec.
@@ -338,6 +361,9 @@ where
     /// Requests that have not yet been sent and are waiting for a connection
     /// to be established.
     pending_outbound_requests: HashMap<PeerId, SmallVec<[RequestProtocol<TCodec>; 10]>>,
+    /// Requests to connected peers that have not yet been sent because
+    /// [`Config::set_max_concurrent_outbound_streams_per_peer`] is reached.
+    queued_outbound_requests: HashMap<PeerId, VecDeque<RequestProtocol<TCodec>>>,
 }
 
 impl<TCodec> Behaviour<TCodec>
@@ -383,6 +409,7 @@ where
             pending_events: VecDeque::new(),
             connected: HashMap::new(),
             pending_outbound_requests: HashMap::new(),
+            queued_outbound_requests: HashMap::new(),
             addresses: HashMap::new(),
         }
     }
@@ -393,6 +420,11 @@ where
     /// attempt is initiated and the request is sent as soon as a
     /// connection is established.
     ///
+    /// Every request is sent on its own substream, so multiple requests
+    /// to the same peer are in flight concurrently and their responses
+    /// may arrive in any order. The number of requests in flight per peer can be
+    /// limited through [`Config::set_max_concurrent_outbound_streams_per_peer`].
+    ///
     /// > **Note**: In order for such a dialing attempt to succeed,
     /// > the `RequestResonse` protocol must either be embedded
     /// > in another `NetworkBehaviour` that provides peer and
@@ -408,7 +440,7 @@ where
             request,
         };
 
-        if let Some(request) = self.try_send_request(peer, request) {
+        if let Some(request) = self.send_or_queue_request(peer, request) {
             self.pending_events.push_back(ToSwarm::Dial {
                 opts: DialOpts::peer_id(*peer).build(),
             });
@@ -470,6 +502,59 @@ where
         }
     }
 
+    /// Cancels an outbound request initiated by [`Behaviour::send_request`].
+    ///
+    /// A request that is still waiting for a connection to be established is
+    /// dropped without being sent. For a request that is already in flight, the
+    /// eventual response or failure is discarded. Neither [`Event::Message`] nor
+    /// [`Event::OutboundFailure`] is emitted for a cancelled request.
+    ///
+    /// Returns `true` if the request was pending and is now cancelled, `false`
+    /// otherwise.
+    ///
+    /// > **Note**: The substream of a request that is in flight is not reset;
+    /// > it stays open until the response is received or the request times out.
+    pub fn cancel_request(&mut self, request_id: RequestId) -> bool {
+        let queued = self
+            .pending_outbound_requests
+            .iter_mut()
+            .find_map(|(peer, requests)| {
+                let pos = requests.iter().position(|r| r.request_id == request_id)?;
+                requests.remove(pos);
+                Some((*peer, requests.is_empty()))
+            });
+        if let Some((peer, now_empty)) = queued {
+            if now_empty {
+                self.pending_outbound_requests.remove(&peer);
+            }
+            return true;
+        }
+
+        let queued = self
+            .queued_outbound_requests
+            .iter_mut()
+            .find_map(|(peer, requests)| {
+                let pos = requests.iter().position(|r| r.request_id == request_id)?;
+                requests.remove(pos);
+                Some((*peer, requests.is_empty()))
+            });
+        if let Some((peer, now_empty)) = queued {
+            if now_empty {
+                self.queued_outbound_requests.remove(&peer);
+            }
+            return true;
+        }
+
+        for connection in self.connected.values_mut().flatten() {
+            if connection.pending_inbound_responses.remove(&request_id) {
+                connection.cancelled_requests.insert(request_id);
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Checks whether an outbound request to the peer with the provided
     /// [`PeerId`] initiated by [`Behaviour::send_request`] is still
     /// pending, i.e. waiting for a response.
@@ -488,11 +573,25 @@ where
             .pending_outbound_requests
             .get(peer)
             .map(|rps| rps.iter().any(|rp| rp.request_id == *request_id))
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || self
+                .queued_outbound_requests
+                .get(peer)
+                .map(|rps| rps.iter().any(|rp| rp.request_id == *request_id))
+                .unwrap_or(false);
 
         est_conn || pen_conn
     }
 
+    /// Returns the number of outbound requests to the peer that are queued because
+    /// [`Config::set_max_concurrent_outbound_streams_per_peer`] is reached.
+    pub fn num_queued_outbound_requests(&self, peer: &PeerId) -> usize {
+        self.queued_outbound_requests
+            .get(peer)
+            .map(|requests| requests.len())
+            .unwrap_or(0)
+    }
+
     /// Checks whether an inbound request from the peer with the provided
     /// [`PeerId`] is still pending, i.e. waiting for a response by the local
     /// node through [`Behaviour::send_response`].
@@ -513,6 +612,74 @@ where
         request_id
     }
 
+    /// Returns the number of outbound requests to the peer that are in flight,
+    /// including cancelled ones whose streams are still open.
+    fn num_outbound_streams(&self, peer: &PeerId) -> usize {
+        self.connected
+            .get(peer)
+            .map(|connections| {
+                connections
+                    .iter()
+                    .map(|c| c.pending_inbound_responses.len() + c.cancelled_requests.len())
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Sends a request to a connected peer, or queues it if the peer has reached
+    /// [`Config::set_max_concurrent_outbound_streams_per_peer`]. If the peer is
+    /// not currently connected, the given request is returned unchanged.
+    fn send_or_queue_request(
+        &mut self,
+        peer: &PeerId,
+        request: RequestProtocol<TCodec>,
+    ) -> Option<RequestProtocol<TCodec>> {
+        if !self.is_connected(peer) {
+            return Some(request);
+        }
+        if let Some(queue) = self.queued_outbound_requests.get_mut(peer) {
+            queue.push_back(request);
+            return None;
+        }
+        if self.num_outbound_streams(peer) >= self.config.max_concurrent_outbound_streams_per_peer {
+            self.queued_outbound_requests
+                .entry(*peer)
+                .or_default()
+                .push_back(request);
+            return None;
+        }
+        self.try_send_request(peer, request)
+    }
+
+    /// Sends queued requests to peers that are below
+    /// [`Config::set_max_concurrent_outbound_streams_per_peer`] again.
+    fn send_queued_requests(&mut self) {
+        let peers = self
+            .queued_outbound_requests
+            .keys()
+            .filter(|peer| {
+                self.num_outbound_streams(peer)
+                    < self.config.max_concurrent_outbound_streams_per_peer
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for peer in peers {
+            while self.num_outbound_streams(&peer)
+                < self.config.max_concurrent_outbound_streams_per_peer
+            {
+                let Some(queue) = self.queued_outbound_requests.get_mut(&peer) else {
+                    break;
+                };
+                let request = queue.pop_front().expect("queues to be non-empty");
+                if queue.is_empty() {
+                    self.queued_outbound_requests.remove(&peer);
+                }
+                let request = self.try_send_request(&peer, request);
+                debug_assert!(request.is_none(), "Expect queued requests to be connected.");
+            }
+        }
+    }
+
     /// Tries to send a request by queueing an appropriate event to be
     /// emitted to the `Swarm`. If the peer is not currently connected,
     /// the given request is return unchanged.
@@ -571,6 +738,22 @@ where
             .unwrap_or(false)
     }
 
+    /// Remove a request cancelled via [`Behaviour::cancel_request`] for the
+    /// given peer and connection.
+    ///
+    /// Returns `true` if the [`RequestId`] was previously cancelled on this
+    /// connection, in which case the outcome of the request must be discarded.
+    fn remove_cancelled_request(
+        &mut self,
+        peer: &PeerId,
+        connection: ConnectionId,
+        request: &RequestId,
+    ) -> bool {
+        self.get_connection_mut(peer, connection)
+            .map(|c| c.cancelled_requests.remove(request))
+            .unwrap_or(false)
+    }
+
     /// Returns a mutable reference to the connection in `self.connected`
     /// corresponding to the given [`PeerId`] and [`ConnectionId`].
     fn get_connection_mut(
@@ -630,7 +813,7 @@ where
         if other_established == 0 {
             if let Some(pending) = self.pending_outbound_requests.remove(&peer_id) {
                 for request in pending {
-                    let request = self.try_send_request(&peer_id, request);
+                    let request = self.send_or_queue_request(&peer_id, request);
                     assert!(request.is_none());
                 }
             }
@@ -679,6 +862,21 @@ where
                     error: OutboundFailure::ConnectionClosed,
                 }));
         }
+
+        if remaining_established == 0 {
+            for request in self
+                .queued_outbound_requests
+                .remove(&peer_id)
+                .unwrap_or_default()
+            {
+                self.pending_events
+                    .push_back(ToSwarm::GenerateEvent(Event::OutboundFailure {
+                        peer: peer_id,
+                        request_id: request.request_id,
+                        error: OutboundFailure::ConnectionClosed,
+                    }));
+            }
+        }
     }
 
     fn on_dial_failure(&mut self, DialFailure { peer_id, .. }: DialFailure) {
@@ -798,6 +996,9 @@ where
                 request_id,
                 response,
             } => {
+                if self.remove_cancelled_request(&peer, connection, &request_id) {
+                    return;
+                }
                 let removed = self.remove_pending_inbound_response(&peer, connection, &request_id);
                 debug_assert!(
                     removed,
@@ -870,6 +1071,9 @@ where
                     }));
             }
             handler::Event::OutboundTimeout(request_id) => {
+                if self.remove_cancelled_request(&peer, connection, &request_id) {
+                    return;
+                }
                 let removed = self.remove_pending_inbound_response(&peer, connection, &request_id);
                 debug_assert!(
                     removed,
@@ -884,6 +1088,9 @@ where
                     }));
             }
             handler::Event::OutboundUnsupportedProtocols(request_id) => {
+                if self.remove_cancelled_request(&peer, connection, &request_id) {
+                    return;
+                }
                 let removed = self.remove_pending_inbound_response(&peer, connection, &request_id);
                 debug_assert!(
                     removed,
@@ -905,6 +1112,8 @@ where
         _: &mut Context<'_>,
         _: &mut impl PollParameters,
     ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        self.send_queued_requests();
+
         if let Some(ev) = self.pending_events.pop_front() {
             return Poll::Ready(ev);
         } else if self.pending_events.capacity() > EMPTY_QUEUE_SHRINK_THRESHOLD {
@@ -932,6 +1141,9 @@ struct Connection {
     /// Pending inbound responses for previously sent requests on this
     /// connection.
     pending_inbound_responses: HashSet<RequestId>,
+    /// Requests sent on this connection that have been cancelled via
+    /// [`Behaviour::cancel_request`] and whose outcome is yet to be discarded.
+    cancelled_requests: HashSet<RequestId>,
 }
 
 impl Connection {
@@ -941,6 +1153,7 @@ impl Connection {
             address,
             pending_outbound_responses: Default::default(),
             pending_inbound_responses: Default::default(),
+            cancelled_requests: Default::default(),
         }
     }
 }
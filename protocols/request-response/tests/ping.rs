@@ -28,7 +28,7 @@ use libp2p_swarm::{StreamProtocol, Swarm, SwarmEvent};
 use libp2p_swarm_test::SwarmExt;
 use rand::{self, Rng};
 use serde::{Deserialize, Serialize};
-use std::iter;
+use std::{iter, num::NonZeroUsize};
 
 #[async_std::test]
 #[cfg(feature = "cbor")]
@@ -173,6 +173,83 @@ async fn ping_protocol() {
     peer2.await;
 }
 
+/// Requests to the same peer are sent on separate substreams and are thus in flight
+/// concurrently, i.e. sending a request does not wait for the responses to earlier ones.
+#[async_std::test]
+#[cfg(feature = "cbor")]
+async fn concurrent_requests() {
+    let ping = Ping("ping".to_string().into_bytes());
+    let pong = Pong("pong".to_string().into_bytes());
+
+    let protocols = iter::once((StreamProtocol::new("/ping/1"), ProtocolSupport::Full));
+    let cfg = request_response::Config::default();
+
+    let mut swarm1 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols.clone(), cfg.clone())
+    });
+    let peer1_id = *swarm1.local_peer_id();
+    let mut swarm2 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols, cfg)
+    });
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let num_requests = 10;
+
+    let peer1 = async move {
+        let mut channels = Vec::new();
+        loop {
+            match swarm1.next_swarm_event().await.try_into_behaviour_event() {
+                Ok(request_response::Event::Message {
+                    message: request_response::Message::Request { channel, .. },
+                    ..
+                }) => {
+                    channels.push(channel);
+                    // Only respond once all requests have arrived, in reverse order.
+                    if channels.len() == num_requests {
+                        for channel in channels.drain(..).rev() {
+                            swarm1
+                                .behaviour_mut()
+                                .send_response(channel, pong.clone())
+                                .unwrap();
+                        }
+                    }
+                }
+                Ok(request_response::Event::ResponseSent { .. }) => {}
+                Ok(e) => panic!("Peer1: Unexpected event: {e:?}"),
+                Err(..) => {}
+            }
+        }
+    };
+
+    let peer2 = async {
+        let mut pending = (0..num_requests)
+            .map(|_| swarm2.behaviour_mut().send_request(&peer1_id, ping.clone()))
+            .collect::<Vec<_>>();
+
+        while !pending.is_empty() {
+            match swarm2
+                .next_swarm_event()
+                .await
+                .try_into_behaviour_event()
+                .unwrap()
+            {
+                request_response::Event::Message {
+                    message: request_response::Message::Response { request_id, .. },
+                    ..
+                } => {
+                    pending.retain(|id| *id != request_id);
+                }
+                e => panic!("Peer2: Unexpected event: {e:?}"),
+            }
+        }
+    };
+
+    async_std::task::spawn(Box::pin(peer1));
+    peer2.await;
+}
+
 #[async_std::test]
 #[cfg(feature = "cbor")]
 async fn emits_inbound_connection_closed_failure() {
@@ -291,6 +368,173 @@ async fn emits_inbound_connection_closed_if_channel_is_dropped() {
     assert_eq!(error, request_response::OutboundFailure::ConnectionClosed);
 }
 
+#[async_std::test]
+#[cfg(feature = "cbor")]
+async fn cancelled_request_is_not_reported() {
+    let ping = Ping("ping".to_string().into_bytes());
+    let pong = Pong("pong".to_string().into_bytes());
+
+    let protocols = iter::once((StreamProtocol::new("/ping/1"), ProtocolSupport::Full));
+    let cfg = request_response::Config::default();
+
+    let mut swarm1 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols.clone(), cfg.clone())
+    });
+    let peer1_id = *swarm1.local_peer_id();
+    let mut swarm2 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols, cfg)
+    });
+
+    // A request that is still waiting for a connection is dropped.
+    let queued_id = swarm2.behaviour_mut().send_request(&peer1_id, ping.clone());
+    assert!(swarm2.behaviour_mut().cancel_request(queued_id));
+    assert!(!swarm2
+        .behaviour()
+        .is_pending_outbound(&peer1_id, &queued_id));
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let cancelled_id = swarm2.behaviour_mut().send_request(&peer1_id, ping.clone());
+
+    // Wait for swarm 1 to receive the request by swarm 2.
+    let channel = loop {
+        futures::select!(
+            event = swarm1.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Request { channel, .. },
+                    ..
+                }) => break channel,
+                SwarmEvent::Behaviour(ev) => panic!("Peer1: Unexpected event: {ev:?}"),
+                _ => {}
+            },
+            event = swarm2.select_next_some() => {
+                if let SwarmEvent::Behaviour(ev) = event {
+                    panic!("Peer2: Unexpected event: {ev:?}");
+                }
+            }
+        )
+    };
+
+    assert!(swarm2.behaviour_mut().cancel_request(cancelled_id));
+    assert!(!swarm2.behaviour_mut().cancel_request(cancelled_id));
+    assert!(!swarm2
+        .behaviour()
+        .is_pending_outbound(&peer1_id, &cancelled_id));
+
+    swarm1
+        .behaviour_mut()
+        .send_response(channel, pong.clone())
+        .unwrap();
+    let request_id = swarm2.behaviour_mut().send_request(&peer1_id, ping);
+
+    // Only the response to the request that was not cancelled is reported.
+    loop {
+        futures::select!(
+            event = swarm1.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Request { channel, .. },
+                    ..
+                }) => {
+                    swarm1
+                        .behaviour_mut()
+                        .send_response(channel, pong.clone())
+                        .unwrap();
+                }
+                SwarmEvent::Behaviour(request_response::Event::ResponseSent { .. }) => {}
+                SwarmEvent::Behaviour(ev) => panic!("Peer1: Unexpected event: {ev:?}"),
+                _ => {}
+            },
+            event = swarm2.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Response { request_id: id, .. },
+                    ..
+                }) => {
+                    assert_eq!(id, request_id);
+                    break;
+                }
+                SwarmEvent::Behaviour(ev) => panic!("Peer2: Unexpected event: {ev:?}"),
+                _ => {}
+            }
+        )
+    }
+}
+
+/// Outbound requests beyond the configured per-peer limit are queued and
+/// sent one after another as earlier requests complete.
+#[async_std::test]
+#[cfg(feature = "cbor")]
+async fn outbound_requests_are_queued_beyond_limit() {
+    let ping = Ping("ping".to_string().into_bytes());
+    let pong = Pong("pong".to_string().into_bytes());
+
+    let protocols = iter::once((StreamProtocol::new("/ping/1"), ProtocolSupport::Full));
+    let mut cfg = request_response::Config::default();
+    cfg.set_max_concurrent_outbound_streams_per_peer(NonZeroUsize::new(1).unwrap());
+
+    let mut swarm1 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols.clone(), cfg.clone())
+    });
+    let peer1_id = *swarm1.local_peer_id();
+    let mut swarm2 = Swarm::new_ephemeral(|_| {
+        request_response::cbor::Behaviour::<Ping, Pong>::new(protocols, cfg)
+    });
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let request_ids = (0..3)
+        .map(|_| swarm2.behaviour_mut().send_request(&peer1_id, ping.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        swarm2.behaviour().num_queued_outbound_requests(&peer1_id),
+        2
+    );
+    for id in &request_ids {
+        assert!(swarm2.behaviour().is_pending_outbound(&peer1_id, id));
+    }
+
+    let mut pending_requests = 0;
+    let mut responses = Vec::new();
+    while responses.len() < request_ids.len() {
+        futures::select!(
+            event = swarm1.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Request { channel, .. },
+                    ..
+                }) => {
+                    pending_requests += 1;
+                    assert_eq!(pending_requests, 1, "Expect at most one request in flight");
+                    swarm1
+                        .behaviour_mut()
+                        .send_response(channel, pong.clone())
+                        .unwrap();
+                }
+                SwarmEvent::Behaviour(request_response::Event::ResponseSent { .. }) => {}
+                SwarmEvent::Behaviour(ev) => panic!("Peer1: Unexpected event: {ev:?}"),
+                _ => {}
+            },
+            event = swarm2.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Response { request_id, .. },
+                    ..
+                }) => {
+                    pending_requests -= 1;
+                    responses.push(request_id);
+                }
+                SwarmEvent::Behaviour(ev) => panic!("Peer2: Unexpected event: {ev:?}"),
+                _ => {}
+            }
+        )
+    }
+
+    assert_eq!(responses, request_ids);
+    assert_eq!(
+        swarm2.behaviour().num_queued_outbound_requests(&peer1_id),
+        0
+    );
+}
+
 // Simple Ping-Pong Protocol
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct Ping(Vec<u8>);
@@ -0,0 +1,137 @@
+//! Tests for responses that are streamed from an [`AsyncRead`] rather than buffered.
+
+use async_trait::async_trait;
+use futures::prelude::*;
+use libp2p_request_response as request_response;
+use libp2p_request_response::ProtocolSupport;
+use libp2p_swarm::{StreamProtocol, Swarm, SwarmEvent};
+use libp2p_swarm_test::SwarmExt;
+use std::{fmt, io, iter};
+
+const PAYLOAD_SIZE: usize = 1024 * 1024;
+
+#[async_std::test]
+async fn response_is_streamed_from_reader() {
+    let _ = env_logger::try_init();
+
+    let payload = (0..PAYLOAD_SIZE).map(|i| i as u8).collect::<Vec<_>>();
+
+    let protocols = iter::once((StreamProtocol::new("/file/1"), ProtocolSupport::Full));
+    let cfg = request_response::Config::default();
+
+    let mut swarm1 = Swarm::new_ephemeral(|_| {
+        request_response::Behaviour::with_codec(FileCodec, protocols.clone(), cfg.clone())
+    });
+    let peer1_id = *swarm1.local_peer_id();
+    let mut swarm2 = Swarm::new_ephemeral(|_| {
+        request_response::Behaviour::with_codec(FileCodec, protocols, cfg)
+    });
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let request_id = swarm2.behaviour_mut().send_request(&peer1_id, ());
+
+    let received = loop {
+        futures::select!(
+            event = swarm1.select_next_some() => {
+                if let SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Request { channel, .. },
+                    ..
+                }) = event {
+                    let reader = Box::new(futures::io::Cursor::new(payload.clone()));
+                    swarm1
+                        .behaviour_mut()
+                        .send_response(channel, File::Reader(reader))
+                        .unwrap();
+                }
+            },
+            event = swarm2.select_next_some() => match event {
+                SwarmEvent::Behaviour(request_response::Event::Message {
+                    message: request_response::Message::Response { request_id: id, response },
+                    ..
+                }) => {
+                    assert_eq!(id, request_id);
+                    break response;
+                }
+                SwarmEvent::Behaviour(ev) => panic!("Peer2: Unexpected event: {ev:?}"),
+                _ => {}
+            }
+        )
+    };
+
+    match received {
+        File::Bytes(bytes) => assert!(bytes == payload, "Payload was corrupted"),
+        File::Reader(_) => panic!("Expected the received response to be read to the end"),
+    }
+}
+
+/// A response that is written from a reader and read into memory.
+enum File {
+    Reader(Box<dyn AsyncRead + Send + Unpin>),
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Debug for File {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            File::Reader(_) => f.write_str("File::Reader"),
+            File::Bytes(bytes) => write!(f, "File::Bytes({} bytes)", bytes.len()),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct FileCodec;
+
+#[async_trait]
+impl request_response::Codec for FileCodec {
+    type Protocol = StreamProtocol;
+    type Request = ();
+    type Response = File;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, _: &mut T) -> io::Result<()>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        Ok(())
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<File>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut bytes = Vec::new();
+        io.take(2 * PAYLOAD_SIZE as u64)
+            .read_to_end(&mut bytes)
+            .await?;
+
+        Ok(File::Bytes(bytes))
+    }
+
+    async fn write_request<T>(&mut self, _: &StreamProtocol, _: &mut T, _: ()) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        Ok(())
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        res: File,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        match res {
+            File::Reader(reader) => {
+                futures::io::copy(reader, io).await?;
+            }
+            File::Bytes(bytes) => io.write_all(&bytes).await?,
+        }
+
+        Ok(())
+    }
+}
@@ -25,6 +25,12 @@
 //! This struct will automatically try to map the ports externally to internal
 //! addresses on the gateway.
 //!
+//! The gateway is discovered via SSDP and a TCP or UDP port mapping is requested for every
+//! address the node listens on. Mappings are renewed before they expire.
+//! Once the gateway has mapped a port, the resulting external address is reported to the
+//! [`Swarm`](libp2p_swarm::Swarm) as confirmed, so other behaviours, e.g. AutoNAT, learn about it
+//! without having to probe it first. If a renewal fails, the address is reported as expired.
+//!
 
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
@@ -18,6 +18,13 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+//! Utilities for testing [`NetworkBehaviour`]s with real [`Swarm`]s but without a real network.
+//!
+//! [`SwarmExt::new_ephemeral`] creates [`Swarm`]s that communicate over an in-memory transport,
+//! so connections are established, and events are emitted, exactly like in production,
+//! without opening any sockets. Prefer this over synthesizing [`SwarmEvent`]s: the events
+//! a test observes are then the ones the behaviour under test actually produces.
+
 use async_trait::async_trait;
 use futures::future::Either;
 use futures::StreamExt;
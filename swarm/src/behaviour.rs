@@ -88,6 +88,11 @@ use std::{task::Context, task::Poll};
 /// custom `to_swarm` is handled by [`From`] implementations which the user needs to define in
 /// addition to the event `enum` itself.
 ///
+/// Every `struct` member must itself be a [`NetworkBehaviour`]; there is no way to exclude a field
+/// from delegation. State that isn't a behaviour, e.g. a queue of application events, belongs next
+/// to the [`Swarm`](crate::Swarm) rather than inside the derived `struct`. Alternatively, implement
+/// [`NetworkBehaviour`] by hand on a `struct` that wraps the derived one together with that state.
+///
 /// ``` rust
 /// # use libp2p_identify as identify;
 /// # use libp2p_ping as ping;
@@ -129,7 +134,11 @@ pub trait NetworkBehaviour: 'static {
     /// At this point in the connection lifecycle, only the remote's and our local address are known.
     /// We have also already allocated a [`ConnectionId`].
     ///
-    /// Any error returned from this function will immediately abort the dial attempt.
+    /// Any error returned from this function will immediately abort the incoming connection,
+    /// before any of the cost of upgrading it (e.g. the security handshake) is paid.
+    /// The transport has accepted the connection, but not a single byte has been read from or
+    /// written to it, thus this is the place to filter inbound connections by address, e.g. to
+    /// mitigate connection floods. See `libp2p-allow-block-list` and `libp2p-connection-limits`.
     fn handle_pending_inbound_connection(
         &mut self,
         _connection_id: ConnectionId,
@@ -166,6 +175,10 @@ pub trait NetworkBehaviour: 'static {
     /// Note that the addresses returned from this function are only used for dialing if [`WithPeerIdWithAddresses::extend_addresses_through_behaviour`](crate::dial_opts::WithPeerIdWithAddresses::extend_addresses_through_behaviour) is set.
     ///
     /// Any error returned from this function will immediately abort the dial attempt.
+    ///
+    /// This function is synchronous. Behaviours that look up addresses asynchronously, e.g. in
+    /// a database or a DHT, should do so before dialing, driving the lookup from
+    /// [`NetworkBehaviour::poll`], and then emit [`ToSwarm::Dial`] with the addresses found.
     fn handle_pending_outbound_connection(
         &mut self,
         _connection_id: ConnectionId,
@@ -231,6 +244,21 @@ pub trait PollParameters {
         note = "Use `libp2p_swarm::SupportedProtocols` in your `ConnectionHandler` instead."
     )]
     fn supported_protocols(&self) -> Self::SupportedProtocolsIter;
+
+    /// Returns the confirmed external addresses of the local node.
+    fn external_addresses(&self) -> Box<dyn Iterator<Item = &Multiaddr> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Returns the addresses the local node is listening on.
+    fn listen_addresses(&self) -> Box<dyn Iterator<Item = &Multiaddr> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Returns the peers the local node has at least one established connection to.
+    fn connected_peers(&self) -> Box<dyn Iterator<Item = &PeerId> + '_> {
+        Box::new(std::iter::empty())
+    }
 }
 
 /// A command issued from a [`NetworkBehaviour`] for the [`Swarm`].
@@ -314,6 +342,12 @@ pub enum ToSwarm<TOutEvent, TInEvent> {
     /// either in a collaborative manner across [`ConnectionHandler`]s
     /// with [`ConnectionHandler::connection_keep_alive`] or directly with
     /// [`ConnectionHandlerEvent::Close`](crate::ConnectionHandlerEvent::Close).
+    ///
+    /// To disconnect a peer, e.g. when banning it, use [`CloseConnection::All`], which closes
+    /// all of its connections without the behaviour having to track their [`ConnectionId`]s.
+    /// New connections to a banned peer can be refused in
+    /// [`NetworkBehaviour::handle_established_inbound_connection`] and
+    /// [`NetworkBehaviour::handle_established_outbound_connection`].
     CloseConnection {
         /// The peer to disconnect.
         peer_id: PeerId,
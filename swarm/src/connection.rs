@@ -34,6 +34,7 @@ use crate::handler::{
     FullyNegotiatedOutbound, ListenUpgradeError, ProtocolSupport, ProtocolsAdded, ProtocolsChange,
     UpgradeInfoSend,
 };
+use crate::stream::StreamCounter;
 use crate::upgrade::{InboundUpgradeSend, OutboundUpgradeSend};
 use crate::{
     ConnectionHandlerEvent, KeepAlive, Stream, StreamProtocol, StreamUpgradeError,
@@ -65,6 +66,7 @@ static NEXT_CONNECTION_ID: AtomicUsize = AtomicUsize::new(1);
 
 /// Connection identifier.
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConnectionId(usize);
 
 impl ConnectionId {
@@ -157,6 +159,13 @@ where
     local_supported_protocols: HashSet<StreamProtocol>,
     remote_supported_protocols: HashSet<StreamProtocol>,
     idle_timeout: Duration,
+    /// Deadline after which the connection is closed even if the handler keeps it alive.
+    ///
+    /// Set by [`Connection::close_when_idle`].
+    close_deadline: Option<Delay>,
+    /// Counts the negotiated streams of this connection that have been handed to the handler and
+    /// not dropped yet.
+    stream_counter: StreamCounter,
 }
 
 impl<THandler> fmt::Debug for Connection<THandler>
@@ -205,14 +214,31 @@ where
             local_supported_protocols: initial_protocols,
             remote_supported_protocols: Default::default(),
             idle_timeout,
+            close_deadline: None,
+            stream_counter: StreamCounter::default(),
         }
     }
 
+    /// Returns a handle to the number of open streams of this connection.
+    pub(crate) fn stream_counter(&self) -> StreamCounter {
+        self.stream_counter.clone()
+    }
+
     /// Notifies the connection handler of an event.
     pub(crate) fn on_behaviour_event(&mut self, event: THandler::FromBehaviour) {
         self.handler.on_behaviour_event(event);
     }
 
+    /// Closes the connection as soon as the handler no longer keeps it alive,
+    /// disregarding the idle timeout, but at the latest after `timeout`.
+    ///
+    /// The connection then fails with [`ConnectionError::KeepAliveTimeout`] or
+    /// with an [`io::ErrorKind::TimedOut`] error respectively.
+    pub(crate) fn close_when_idle(&mut self, timeout: Duration) {
+        self.idle_timeout = Duration::ZERO;
+        self.close_deadline = Some(Delay::new(timeout));
+    }
+
     /// Begins an orderly shutdown of the connection, returning the connection
     /// handler and a `Future` that resolves when connection shutdown is complete.
     pub(crate) fn close(self) -> (THandler, impl Future<Output = io::Result<()>>) {
@@ -237,6 +263,8 @@ where
             local_supported_protocols: supported_protocols,
             remote_supported_protocols,
             idle_timeout,
+            close_deadline,
+            stream_counter,
         } = self.get_mut();
 
         loop {
@@ -339,7 +367,7 @@ where
                     continue;
                 }
                 Poll::Ready(Some((_, Err(StreamUpgradeError::Timeout)))) => {
-                    log::debug!("inbound stream upgrade timed out");
+                    // Already logged by the `StreamUpgrade` together with the offered protocols.
                     continue;
                 }
             }
@@ -387,6 +415,15 @@ where
                 (_, KeepAlive::Yes) => *shutdown = Shutdown::None,
             };
 
+            if let Some(deadline) = close_deadline {
+                if deadline.poll_unpin(cx).is_ready() {
+                    return Poll::Ready(Err(ConnectionError::IO(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "connection did not become idle before the close timeout",
+                    ))));
+                }
+            }
+
             // Check if the connection (and handler) should be shut down.
             // As long as we're still negotiating substreams, shutdown is always postponed.
             if negotiating_in.is_empty()
@@ -427,6 +464,7 @@ where
                             timeout,
                             upgrade,
                             *substream_upgrade_protocol_override,
+                            stream_counter.clone(),
                         ));
 
                         continue; // Go back to the top, handler can potentially make progress again.
@@ -440,7 +478,11 @@ where
                     Poll::Ready(substream) => {
                         let protocol = handler.listen_protocol();
 
-                        negotiating_in.push(StreamUpgrade::new_inbound(substream, protocol));
+                        negotiating_in.push(StreamUpgrade::new_inbound(
+                            substream,
+                            protocol,
+                            stream_counter.clone(),
+                        ));
 
                         continue; // Go back to the top, handler can potentially make progress again.
                     }
@@ -517,6 +559,10 @@ impl<'a> IncomingInfo<'a> {
 struct StreamUpgrade<UserData, TOk, TErr> {
     user_data: Option<UserData>,
     timeout: Delay,
+    /// The protocols offered for this stream, logged if the upgrade times out.
+    ///
+    /// Empty unless debug logging is enabled, see [`protocol_names`].
+    protocols: Vec<String>,
     upgrade: BoxFuture<'static, Result<TOk, StreamUpgradeError<TErr>>>,
 }
 
@@ -527,6 +573,7 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
         timeout: Delay,
         upgrade: Upgrade,
         version_override: Option<upgrade::Version>,
+        counter: StreamCounter,
     ) -> Self
     where
         Upgrade: OutboundUpgradeSend<Output = TOk, Error = TErr>,
@@ -543,11 +590,13 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
             }
             _ => upgrade::Version::default(),
         };
-        let protocols = upgrade.protocol_info();
+        let protocols = upgrade.protocol_info().collect::<Vec<_>>();
+        let offered = protocol_names(&protocols);
 
         Self {
             user_data: Some(user_data),
             timeout,
+            protocols: offered.clone(),
             upgrade: Box::pin(async move {
                 let (info, stream) = multistream_select::dialer_select_proto(
                     substream,
@@ -555,10 +604,18 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
                     effective_version,
                 )
                 .await
-                .map_err(to_stream_upgrade_error)?;
+                .map_err(|e| {
+                    if matches!(e, NegotiationError::Failed) {
+                        log::debug!(
+                            "no protocol could be agreed upon for outbound stream, offered {offered:?}"
+                        );
+                    }
+
+                    to_stream_upgrade_error(e)
+                })?;
 
                 let output = upgrade
-                    .upgrade_outbound(Stream::new(stream), info)
+                    .upgrade_outbound(Stream::new(stream, counter), info)
                     .await
                     .map_err(StreamUpgradeError::Apply)?;
 
@@ -572,17 +629,19 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
     fn new_inbound<Upgrade>(
         substream: SubstreamBox,
         protocol: SubstreamProtocol<Upgrade, UserData>,
+        counter: StreamCounter,
     ) -> Self
     where
         Upgrade: InboundUpgradeSend<Output = TOk, Error = TErr>,
     {
         let timeout = *protocol.timeout();
         let (upgrade, open_info) = protocol.into_upgrade();
-        let protocols = upgrade.protocol_info();
+        let protocols = upgrade.protocol_info().collect::<Vec<_>>();
 
         Self {
             user_data: Some(open_info),
             timeout: Delay::new(timeout),
+            protocols: protocol_names(&protocols),
             upgrade: Box::pin(async move {
                 let (info, stream) =
                     multistream_select::listener_select_proto(substream, protocols)
@@ -590,7 +649,7 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
                         .map_err(to_stream_upgrade_error)?;
 
                 let output = upgrade
-                    .upgrade_inbound(Stream::new(stream), info)
+                    .upgrade_inbound(Stream::new(stream, counter), info)
                     .await
                     .map_err(StreamUpgradeError::Apply)?;
 
@@ -600,6 +659,17 @@ impl<UserData, TOk, TErr> StreamUpgrade<UserData, TOk, TErr> {
     }
 }
 
+/// Returns the names of the given protocols for logging.
+///
+/// To not allocate for every stream, the names are only collected if debug logging is enabled.
+fn protocol_names(protocols: &[impl AsRef<str>]) -> Vec<String> {
+    if !log::log_enabled!(log::Level::Debug) {
+        return Vec::new();
+    }
+
+    protocols.iter().map(|p| p.as_ref().to_owned()).collect()
+}
+
 fn to_stream_upgrade_error<T>(e: NegotiationError) -> StreamUpgradeError<T> {
     match e {
         NegotiationError::Failed => StreamUpgradeError::NegotiationFailed,
@@ -618,12 +688,14 @@ impl<UserData, TOk, TErr> Future for StreamUpgrade<UserData, TOk, TErr> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         match self.timeout.poll_unpin(cx) {
             Poll::Ready(()) => {
+                log::debug!("stream upgrade for {:?} timed out", self.protocols);
+
                 return Poll::Ready((
                     self.user_data
                         .take()
                         .expect("Future not to be polled again once ready."),
                     Err(StreamUpgradeError::Timeout),
-                ))
+                ));
             }
 
             Poll::Pending => {}
@@ -975,6 +1047,55 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn close_when_idle_with_keep_alive_no() {
+        let mut connection = Connection::new(
+            StreamMuxerBox::new(PendingStreamMuxer),
+            dummy::ConnectionHandler,
+            None,
+            0,
+            Duration::from_secs(10),
+        );
+
+        assert!(connection.poll_noop_waker().is_pending());
+
+        connection.close_when_idle(Duration::from_secs(10));
+
+        assert!(
+            matches!(
+                connection.poll_noop_waker(),
+                Poll::Ready(Err(ConnectionError::KeepAliveTimeout))
+            ),
+            "idle connection should close without waiting for the idle-timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn close_when_idle_with_keep_alive_until_greater_than_timeout() {
+        let timeout = Duration::from_millis(100);
+
+        let mut connection = Connection::new(
+            StreamMuxerBox::new(PendingStreamMuxer),
+            KeepAliveUntilConnectionHandler {
+                until: Instant::now() + timeout * 10,
+            },
+            None,
+            0,
+            Duration::ZERO,
+        );
+
+        connection.close_when_idle(timeout);
+
+        assert!(connection.poll_noop_waker().is_pending());
+
+        tokio::time::sleep(timeout).await;
+
+        assert!(matches!(
+            connection.poll_noop_waker(),
+            Poll::Ready(Err(ConnectionError::IO(e))) if e.kind() == io::ErrorKind::TimedOut
+        ));
+    }
+
     #[test]
     fn checked_add_fraction_can_add_u64_max() {
         let _ = env_logger::try_init();
@@ -19,6 +19,7 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 use crate::connection::{Connection, ConnectionId, PendingPoint};
+use crate::stream::StreamCounter;
 use crate::{
     connection::{
         Connected, ConnectionError, IncomingInfo, PendingConnectionError,
@@ -145,6 +146,8 @@ pub(crate) struct EstablishedConnection<TInEvent> {
     endpoint: ConnectedPoint,
     /// Channel endpoint to send commands to the task.
     sender: mpsc::Sender<task::Command<TInEvent>>,
+    /// Number of negotiated streams on the connection that have not been dropped yet.
+    stream_counter: StreamCounter,
 }
 
 impl<TInEvent> EstablishedConnection<TInEvent> {
@@ -179,6 +182,22 @@ impl<TInEvent> EstablishedConnection<TInEvent> {
         self.sender.poll_ready(cx).map_err(|_| ())
     }
 
+    /// Closes the connection once it is idle, but at the latest after `timeout`.
+    ///
+    /// Has no effect if the connection is already closing.
+    pub(crate) fn start_close_when_idle(&mut self, timeout: Duration) {
+        // Clone the sender so that we are guaranteed to have
+        // capacity for the close command (every sender gets a slot).
+        match self
+            .sender
+            .clone()
+            .try_send(task::Command::CloseWhenIdle(timeout))
+        {
+            Ok(()) => {}
+            Err(e) => assert!(e.is_disconnected(), "No capacity for close command."),
+        };
+    }
+
     /// Initiates a graceful close of the connection.
     ///
     /// Has no effect if the connection is already closing.
@@ -363,6 +382,24 @@ where
         self.established.len()
     }
 
+    /// Closes all established connections to the given peer once they are idle,
+    /// but at the latest after `timeout`, and aborts pending ones.
+    pub(crate) fn disconnect_when_idle(&mut self, peer: PeerId, timeout: Duration) {
+        if let Some(conns) = self.established.get_mut(&peer) {
+            for (_, conn) in conns.iter_mut() {
+                conn.start_close_when_idle(timeout);
+            }
+        }
+
+        for connection in self
+            .pending
+            .iter_mut()
+            .filter_map(|(_, info)| info.is_for_same_remote_as(peer).then_some(info))
+        {
+            connection.abort()
+        }
+    }
+
     /// (Forcefully) close all connections to the given peer.
     ///
     /// All connections to the peer, whether pending or established are
@@ -395,6 +432,45 @@ where
         }
     }
 
+    /// Returns the number of open streams across all established connections of `peer`.
+    pub(crate) fn num_streams_of_peer(&self, peer: &PeerId) -> usize {
+        self.established
+            .get(peer)
+            .map(|conns| conns.values().map(|conn| conn.stream_counter.get()).sum())
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of open streams across all established connections.
+    pub(crate) fn num_streams(&self) -> usize {
+        self.established
+            .values()
+            .flat_map(|conns| conns.values())
+            .map(|conn| conn.stream_counter.get())
+            .sum()
+    }
+
+    /// Returns an iterator over the endpoints of all established connections.
+    pub(crate) fn iter_established_endpoints(&self) -> impl Iterator<Item = &ConnectedPoint> {
+        self.established
+            .values()
+            .flat_map(|conns| conns.values().map(|conn| &conn.endpoint))
+    }
+
+    /// Returns an iterator over the connected peers and the endpoints of their established
+    /// connections.
+    pub(crate) fn iter_established_by_peer(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &PeerId,
+            impl Iterator<Item = (ConnectionId, &ConnectedPoint)>,
+        ),
+    > {
+        self.established
+            .iter()
+            .map(|(peer, conns)| (peer, conns.iter().map(|(id, conn)| (*id, &conn.endpoint))))
+    }
+
     /// Checks whether we are currently dialing the given peer.
     pub(crate) fn is_dialing(&self, peer: PeerId) -> bool {
         self.pending.iter().any(|(_, info)| {
@@ -497,7 +573,13 @@ where
         connection: NewConnection,
         handler: THandler,
     ) {
-        let connection = connection.extract();
+        let connection = Connection::new(
+            connection.extract(),
+            handler,
+            self.substream_upgrade_protocol_override,
+            self.max_negotiating_inbound_streams,
+            self.idle_connection_timeout,
+        );
 
         let conns = self.established.entry(obtained_peer_id).or_default();
         self.counters.inc_established(endpoint);
@@ -510,6 +592,7 @@ where
             EstablishedConnection {
                 endpoint: endpoint.clone(),
                 sender: command_sender,
+                stream_counter: connection.stream_counter(),
             },
         );
         self.established_connection_events.push(event_receiver);
@@ -517,14 +600,6 @@ where
             waker.wake();
         }
 
-        let connection = Connection::new(
-            connection,
-            handler,
-            self.substream_upgrade_protocol_override,
-            self.max_negotiating_inbound_streams,
-            self.idle_connection_timeout,
-        );
-
         self.executor.spawn(task::new_for_established_connection(
             id,
             obtained_peer_id,
@@ -37,6 +37,7 @@ use futures::{
 };
 use libp2p_core::muxing::StreamMuxerBox;
 use std::pin::Pin;
+use std::time::Duration;
 use void::Void;
 
 /// Commands that can be sent to a task driving an established connection.
@@ -47,6 +48,9 @@ pub(crate) enum Command<T> {
     /// Gracefully close the connection (active close) before
     /// terminating the task.
     Close,
+    /// Close the connection once the handler no longer keeps it alive,
+    /// but at the latest after the given timeout.
+    CloseWhenIdle(Duration),
 }
 
 pub(crate) enum PendingConnectionEvent {
@@ -176,6 +180,8 @@ pub(crate) async fn new_for_established_connection<THandler>(
 ) where
     THandler: ConnectionHandler,
 {
+    let mut closing_when_idle = false;
+
     loop {
         match futures::future::select(
             command_receiver.next(),
@@ -200,6 +206,10 @@ pub(crate) async fn new_for_established_connection<THandler>(
                         .await;
                     return;
                 }
+                Command::CloseWhenIdle(timeout) => {
+                    closing_when_idle = true;
+                    connection.close_when_idle(timeout);
+                }
             },
 
             // The manager has disappeared; abort.
@@ -225,6 +235,21 @@ pub(crate) async fn new_for_established_connection<THandler>(
                             })
                             .await;
                     }
+                    Err(ConnectionError::KeepAliveTimeout) if closing_when_idle => {
+                        command_receiver.close();
+                        let (handler, closing_muxer) = connection.close();
+
+                        let error = closing_muxer.await.err().map(ConnectionError::IO);
+                        let _ = events
+                            .send(EstablishedConnectionEvent::Closed {
+                                id: connection_id,
+                                peer_id,
+                                error,
+                                handler,
+                            })
+                            .await;
+                        return;
+                    }
                     Err(error) => {
                         command_receiver.close();
                         let (handler, _closing_muxer) = connection.close();
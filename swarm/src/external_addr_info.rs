@@ -0,0 +1,52 @@
+use instant::Instant;
+use libp2p_core::Multiaddr;
+
+/// A **confirmed** external address of the local node, together with how often and when it was
+/// confirmed.
+///
+/// See [`Swarm::external_addresses_with_info`](crate::Swarm::external_addresses_with_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalAddrInfo {
+    address: Multiaddr,
+    confirmations: usize,
+    first_confirmed: Instant,
+    last_confirmed: Instant,
+}
+
+impl ExternalAddrInfo {
+    pub(crate) fn new(address: Multiaddr, now: Instant) -> Self {
+        Self {
+            address,
+            confirmations: 1,
+            first_confirmed: now,
+            last_confirmed: now,
+        }
+    }
+
+    pub(crate) fn confirm(&mut self, now: Instant) {
+        self.confirmations += 1;
+        self.last_confirmed = now;
+    }
+
+    /// The external address.
+    pub fn address(&self) -> &Multiaddr {
+        &self.address
+    }
+
+    /// How many times the address was confirmed, either by a
+    /// [`NetworkBehaviour`](crate::NetworkBehaviour) or through
+    /// [`Swarm::add_external_address`](crate::Swarm::add_external_address).
+    pub fn confirmations(&self) -> usize {
+        self.confirmations
+    }
+
+    /// When the address was confirmed for the first time.
+    pub fn first_confirmed(&self) -> Instant {
+        self.first_confirmed
+    }
+
+    /// When the address was confirmed most recently.
+    pub fn last_confirmed(&self) -> Instant {
+        self.last_confirmed
+    }
+}
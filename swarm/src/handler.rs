@@ -298,6 +298,10 @@ impl<'a, IP: InboundUpgradeSend, OP: OutboundUpgradeSend, IOI, OOI>
 /// of simultaneously open negotiated inbound substreams. In other words it is up to the
 /// [`ConnectionHandler`] implementation to stop a malicious remote node to open and keep alive
 /// an excessive amount of inbound substreams.
+///
+/// To measure how long the upgrade took, put an [`Instant`] into the
+/// `info` returned from [`ConnectionHandler::listen_protocol`], which is called whenever the
+/// remote opens a new stream.
 #[derive(Debug)]
 pub struct FullyNegotiatedInbound<IP: InboundUpgradeSend, IOI> {
     pub protocol: IP::Output,
@@ -308,6 +312,9 @@ pub struct FullyNegotiatedInbound<IP: InboundUpgradeSend, IOI> {
 ///
 /// The `protocol` field is the information that was previously passed to
 /// [`ConnectionHandlerEvent::OutboundSubstreamRequest`].
+///
+/// To measure how long opening and upgrading the stream took, put an
+/// [`Instant`] into that `info` when requesting the stream.
 #[derive(Debug)]
 pub struct FullyNegotiatedOutbound<OP: OutboundUpgradeSend, OOI> {
     pub protocol: OP::Output,
@@ -438,6 +445,10 @@ impl<'a> Iterator for ProtocolsRemoved<'a> {
 
 /// [`ConnectionEvent`] variant that informs the handler
 /// that upgrading an outbound substream to the given protocol has failed.
+///
+/// This is the only place where a failed protocol negotiation is reported, see
+/// [`StreamUpgradeError::NegotiationFailed`]. Handlers that want to surface it to the
+/// application can forward it to their [`NetworkBehaviour`](crate::NetworkBehaviour).
 #[derive(Debug)]
 pub struct DialUpgradeError<OOI, OP: OutboundUpgradeSend> {
     pub info: OOI,
@@ -671,6 +682,10 @@ pub enum StreamUpgradeError<TUpgrErr> {
     /// The upgrade produced an error.
     Apply(TUpgrErr),
     /// No protocol could be agreed upon.
+    ///
+    /// The remote supports none of the protocols returned by the upgrade's
+    /// [`UpgradeInfo::protocol_info`](libp2p_core::UpgradeInfo::protocol_info).
+    /// Only the stream is affected, the connection stays open.
     NegotiationFailed,
     /// An IO or otherwise unrecoverable error happened.
     Io(io::Error),
@@ -57,6 +57,9 @@
 
 mod connection;
 mod executor;
+mod external_addr_info;
+#[cfg(feature = "serde")]
+mod serde_error;
 mod stream;
 mod stream_protocol;
 #[cfg(test)]
@@ -72,6 +75,7 @@ pub mod handler;
 )]
 pub mod keep_alive;
 mod listen_opts;
+mod transport_protocol;
 
 /// Bundles all symbols required for the [`libp2p_swarm_derive::NetworkBehaviour`] macro.
 #[doc(hidden)]
@@ -119,6 +123,7 @@ pub use behaviour::{
 pub use connection::pool::ConnectionCounters;
 pub use connection::{ConnectionError, ConnectionId, SupportedProtocols};
 pub use executor::Executor;
+pub use external_addr_info::ExternalAddrInfo;
 pub use handler::{
     ConnectionHandler, ConnectionHandlerEvent, ConnectionHandlerSelect, KeepAlive, OneShotHandler,
     OneShotHandlerConfig, StreamUpgradeError, SubstreamProtocol,
@@ -128,6 +133,7 @@ pub use libp2p_swarm_derive::NetworkBehaviour;
 pub use listen_opts::ListenOpts;
 pub use stream::Stream;
 pub use stream_protocol::{InvalidProtocol, StreamProtocol};
+pub use transport_protocol::TransportProtocol;
 
 use crate::behaviour::ExternalAddrConfirmed;
 use crate::handler::UpgradeInfoSend;
@@ -138,6 +144,7 @@ use connection::{
 };
 use dial_opts::{DialOpts, PeerCondition};
 use futures::{prelude::*, stream::FusedStream};
+use instant::Instant;
 use libp2p_core::{
     connection::ConnectedPoint,
     multiaddr,
@@ -147,7 +154,7 @@ use libp2p_core::{
 };
 use libp2p_identity::PeerId;
 use smallvec::SmallVec;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map, HashMap, HashSet};
 use std::num::{NonZeroU32, NonZeroU8, NonZeroUsize};
 use std::time::Duration;
 use std::{
@@ -181,7 +188,15 @@ pub type THandlerOutEvent<TBehaviour> = <THandler<TBehaviour> as ConnectionHandl
 pub type THandlerErr<TBehaviour> = <THandler<TBehaviour> as ConnectionHandler>::Error;
 
 /// Event generated by the `Swarm`.
+///
+/// With the `serde` feature, [`SwarmEvent`] implements [`serde::Serialize`], e.g. for structured
+/// logging. Errors are serialized as their [`Display`](fmt::Display) representation.
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(bound(serialize = "TBehaviourOutEvent: serde::Serialize, THandlerErr: fmt::Display"))
+)]
 pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
     /// Event generated by the `NetworkBehaviour`.
     Behaviour(TBehaviourOutEvent),
@@ -199,6 +214,7 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         /// [`Some`] when the new connection is an outgoing connection.
         /// Addresses are dialed concurrently. Contains the addresses and errors
         /// of dial attempts that failed before the one successful dial.
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_error::dial_errors"))]
         concurrent_dial_errors: Option<Vec<(Multiaddr, TransportError<io::Error>)>>,
         /// How long it took to establish this connection
         established_in: std::time::Duration,
@@ -216,6 +232,10 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         num_established: u32,
         /// Reason for the disconnection, if it was not a successful
         /// active close.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "serde_error::option_display")
+        )]
         cause: Option<ConnectionError<THandlerErr>>,
     },
     /// A new connection arrived on a listener and is in the process of protocol negotiation.
@@ -223,6 +243,12 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
     /// A corresponding [`ConnectionEstablished`](SwarmEvent::ConnectionEstablished) or
     /// [`IncomingConnectionError`](SwarmEvent::IncomingConnectionError) event will later be
     /// generated for this connection.
+    ///
+    /// This event is only informational. To reject connections before the upgrade, e.g. based on
+    /// the remote's IP address, implement
+    /// [`NetworkBehaviour::handle_pending_inbound_connection`]. It is called before this event,
+    /// connections it denies are reported as
+    /// [`IncomingConnectionError`](SwarmEvent::IncomingConnectionError) instead.
     IncomingConnection {
         /// Identifier of the connection.
         connection_id: ConnectionId,
@@ -247,6 +273,7 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         /// Address used to send back data to the remote.
         send_back_addr: Multiaddr,
         /// The error that happened.
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_error::display"))]
         error: ListenError,
     },
     /// An error happened on an outbound connection.
@@ -256,9 +283,12 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         /// If known, [`PeerId`] of the peer we tried to reach.
         peer_id: Option<PeerId>,
         /// Error that has been encountered.
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_error::display"))]
         error: DialError,
     },
     /// One of our listeners has reported a new local listening address.
+    ///
+    /// Use [`TransportProtocol::from_multiaddr`] on the `address` to filter by transport.
     NewListenAddr {
         /// The listener that is listening on the new address.
         listener_id: ListenerId,
@@ -282,6 +312,10 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         addresses: Vec<Multiaddr>,
         /// Reason for the closure. Contains `Ok(())` if the stream produced `None`, or `Err`
         /// if the stream produced an error.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "serde_error::result_display")
+        )]
         reason: Result<(), io::Error>,
     },
     /// One of the listeners reported a non-fatal error.
@@ -289,6 +323,7 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
         /// The listener that errored.
         listener_id: ListenerId,
         /// The listener error.
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_error::display"))]
         error: io::Error,
     },
     /// A new dialing attempt has been initiated by the [`NetworkBehaviour`]
@@ -298,6 +333,8 @@ pub enum SwarmEvent<TBehaviourOutEvent, THandlerErr> {
     /// reported if the dialing attempt succeeds, otherwise a
     /// [`OutgoingConnectionError`](SwarmEvent::OutgoingConnectionError) event
     /// is reported.
+    ///
+    /// This event is not emitted for dials initiated via [`Swarm::dial`].
     Dialing {
         /// Identity of the peer that we are connecting to.
         peer_id: Option<PeerId>,
@@ -342,11 +379,14 @@ where
     /// List of protocols that the behaviour says it supports.
     supported_protocols: SmallVec<[Vec<u8>; 16]>,
 
-    confirmed_external_addr: HashSet<Multiaddr>,
+    confirmed_external_addr: HashMap<Multiaddr, ExternalAddrInfo>,
 
     /// Multiaddresses that our listeners are listening on,
     listened_addrs: HashMap<ListenerId, SmallVec<[Multiaddr; 1]>>,
 
+    /// Addresses of remote peers added via [`Swarm::add_peer_address`].
+    peer_addresses: HashMap<PeerId, SmallVec<[Multiaddr; 1]>>,
+
     /// Pending event to be delivered to connection handlers
     /// (or dropped if the peer disconnected) before the `behaviour`
     /// can be polled again.
@@ -375,6 +415,7 @@ where
             supported_protocols: Default::default(),
             confirmed_external_addr: Default::default(),
             listened_addrs: HashMap::new(),
+            peer_addresses: HashMap::new(),
             pending_event: None,
         }
     }
@@ -389,6 +430,24 @@ where
         }
     }
 
+    /// Returns information about the connections underlying the [`Swarm`], broken down by
+    /// [`TransportProtocol`].
+    pub fn network_info_extended(&self) -> ExtendedNetworkInfo {
+        let mut connections_by_transport = HashMap::new();
+        for endpoint in self.pool.iter_established_endpoints() {
+            if let Some(transport) =
+                TransportProtocol::from_multiaddr(endpoint.get_remote_address())
+            {
+                *connections_by_transport.entry(transport).or_default() += 1;
+            }
+        }
+
+        ExtendedNetworkInfo {
+            network_info: self.network_info(),
+            connections_by_transport,
+        }
+    }
+
     /// Starts listening on the given address.
     /// Returns an error if the address is not supported.
     ///
@@ -405,6 +464,10 @@ where
     ///
     /// Returns `true` if there was a listener with this ID, `false`
     /// otherwise.
+    ///
+    /// The listener is closed asynchronously. Once the swarm reports
+    /// [`SwarmEvent::ListenerClosed`] for it, its addresses are released and can be listened on
+    /// again, e.g. to restart a listener on the same port.
     pub fn remove_listener(&mut self, listener_id: ListenerId) -> bool {
         self.transport.remove_listener(listener_id)
     }
@@ -413,6 +476,13 @@ where
     ///
     /// See also [`DialOpts`].
     ///
+    /// Unlike dials initiated by the [`NetworkBehaviour`], no [`SwarmEvent::Dialing`] is emitted
+    /// for dials initiated through this method, as `Ok(())` already confirms that the dial is in
+    /// progress. To trace the dial through the subsequent
+    /// [`ConnectionEstablished`](SwarmEvent::ConnectionEstablished) or
+    /// [`OutgoingConnectionError`](SwarmEvent::OutgoingConnectionError) event, build the
+    /// [`DialOpts`] upfront and remember their [`DialOpts::connection_id`].
+    ///
     /// ```
     /// # use libp2p_swarm::SwarmBuilder;
     /// # use libp2p_swarm::dial_opts::{DialOpts, PeerCondition};
@@ -475,7 +545,14 @@ where
             ) {
                 Ok(addresses) => {
                     if dial_opts.extend_addresses_through_behaviour() {
-                        addresses_from_opts.extend(addresses)
+                        addresses_from_opts.extend(addresses);
+                        addresses_from_opts.extend(
+                            peer_id
+                                .and_then(|p| self.peer_addresses.get(&p))
+                                .into_iter()
+                                .flatten()
+                                .cloned(),
+                        );
                     } else {
                         let num_addresses = addresses.len();
 
@@ -557,6 +634,28 @@ where
         self.listened_addrs.values().flatten()
     }
 
+    /// Returns an iterator that produces the list of addresses we're listening on with the given
+    /// [`TransportProtocol`].
+    pub fn listen_addrs_for_transport(
+        &self,
+        transport: TransportProtocol,
+    ) -> impl Iterator<Item = &Multiaddr> {
+        self.listeners()
+            .filter(move |addr| TransportProtocol::from_multiaddr(addr) == Some(transport))
+    }
+
+    /// Returns an iterator that produces the list of addresses the given listener is listening on.
+    ///
+    /// These are the concrete addresses reported via [`SwarmEvent::NewListenAddr`], e.g.
+    /// `/ip4/127.0.0.1/tcp/4001` for a listener started on `/ip4/0.0.0.0/tcp/0`. Thus the list is
+    /// empty until the [`Swarm`] has been polled and the listener has reported its addresses.
+    pub fn listen_addrs_for_listener(
+        &self,
+        listener_id: ListenerId,
+    ) -> impl Iterator<Item = &Multiaddr> {
+        self.listened_addrs.get(&listener_id).into_iter().flatten()
+    }
+
     /// Returns the peer ID of the swarm passed as parameter.
     pub fn local_peer_id(&self) -> &PeerId {
         &self.local_peer_id
@@ -564,7 +663,26 @@ where
 
     /// List all **confirmed** external address for the local node.
     pub fn external_addresses(&self) -> impl Iterator<Item = &Multiaddr> {
-        self.confirmed_external_addr.iter()
+        self.confirmed_external_addr.keys()
+    }
+
+    /// List all **confirmed** external address for the local node, together with how often and
+    /// when each was confirmed.
+    ///
+    /// The addresses are ordered by descending number of confirmations, the most recently
+    /// confirmed address first among those with the same number.
+    pub fn external_addresses_with_info(&self) -> Vec<ExternalAddrInfo> {
+        let mut addresses = self
+            .confirmed_external_addr
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        addresses.sort_by(|a, b| {
+            b.confirmations()
+                .cmp(&a.confirmations())
+                .then(b.last_confirmed().cmp(&a.last_confirmed()))
+        });
+        addresses
     }
 
     fn add_listener(&mut self, opts: ListenOpts) -> Result<(), TransportError<io::Error>> {
@@ -589,6 +707,20 @@ where
         Ok(())
     }
 
+    /// Add an address of a remote peer, e.g. one that is known from a configuration file.
+    ///
+    /// The address is used when dialing the peer by its [`PeerId`], unless the
+    /// [`DialOpts`] disable extending the addresses through the behaviour. This allows dialing
+    /// peers without first discovering their addresses, e.g. via Kademlia.
+    ///
+    /// Addresses are kept until the [`Swarm`] is dropped.
+    pub fn add_peer_address(&mut self, peer_id: PeerId, addr: Multiaddr) {
+        let addresses = self.peer_addresses.entry(peer_id).or_default();
+        if !addresses.contains(&addr) {
+            addresses.push(addr);
+        }
+    }
+
     /// Add a **confirmed** external address for the local node.
     ///
     /// This function should only be called with addresses that are guaranteed to be reachable.
@@ -598,7 +730,14 @@ where
             .on_swarm_event(FromSwarm::ExternalAddrConfirmed(ExternalAddrConfirmed {
                 addr: &a,
             }));
-        self.confirmed_external_addr.insert(a);
+        let now = Instant::now();
+        match self.confirmed_external_addr.entry(a) {
+            hash_map::Entry::Occupied(mut entry) => entry.get_mut().confirm(now),
+            hash_map::Entry::Vacant(entry) => {
+                let info = ExternalAddrInfo::new(entry.key().clone(), now);
+                entry.insert(info);
+            }
+        }
     }
 
     /// Remove an external address for the local node.
@@ -632,6 +771,34 @@ where
         }
     }
 
+    /// Disconnects a peer by its peer ID once all connections to said peer are idle.
+    ///
+    /// Unlike [`Swarm::disconnect_peer_id`], which closes connections immediately, connections
+    /// are closed as soon as their [`ConnectionHandler`] no longer keeps them alive, e.g. once
+    /// in-flight requests have completed. The configured idle timeout is disregarded.
+    /// Connections still kept alive after `timeout` are closed regardless.
+    ///
+    /// A [`SwarmEvent::ConnectionClosed`] event is emitted for every connection. Its `cause` is
+    /// `None` if the connection was closed once idle and a [`ConnectionError::IO`] error of kind
+    /// [`io::ErrorKind::TimedOut`] if `timeout` elapsed first.
+    ///
+    /// Returns `Ok(())` if there was one or more established connections to the peer.
+    #[allow(clippy::result_unit_err)]
+    pub fn disconnect_peer_id_graceful(
+        &mut self,
+        peer_id: PeerId,
+        timeout: Duration,
+    ) -> Result<(), ()> {
+        let was_connected = self.pool.is_connected(peer_id);
+        self.pool.disconnect_when_idle(peer_id, timeout);
+
+        if was_connected {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
     /// Attempt to gracefully close a connection.
     ///
     /// Closing a connection is asynchronous but this function will return immediately.
@@ -660,6 +827,34 @@ where
         self.pool.iter_connected()
     }
 
+    /// Returns the currently connected peers together with their established connections.
+    pub fn peers(&self) -> impl Iterator<Item = (&PeerId, PeerState)> {
+        self.pool
+            .iter_established_by_peer()
+            .map(|(peer, connections)| {
+                let connections = connections
+                    .map(|(id, endpoint)| (id, endpoint.clone()))
+                    .collect();
+                (peer, PeerState { connections })
+            })
+    }
+
+    /// Returns the number of open streams across all established connections to `peer_id`.
+    ///
+    /// A stream is counted once its protocol has been negotiated and it is handed to the
+    /// [`ConnectionHandler`], until the handler drops it. Streams that are still negotiating their
+    /// protocol are not included.
+    pub fn num_substreams(&self, peer_id: &PeerId) -> usize {
+        self.pool.num_streams_of_peer(peer_id)
+    }
+
+    /// Returns the number of open streams across all established connections.
+    ///
+    /// See [`Swarm::num_substreams`].
+    pub fn total_substreams(&self) -> usize {
+        self.pool.num_streams()
+    }
+
     /// Returns a reference to the provided [`NetworkBehaviour`].
     pub fn behaviour(&self) -> &TBehaviour {
         &self.behaviour
@@ -1194,6 +1389,9 @@ where
                     let behaviour_poll = {
                         let mut parameters = SwarmPollParameters {
                             supported_protocols: &this.supported_protocols,
+                            external_addresses: &this.confirmed_external_addr,
+                            listened_addrs: &this.listened_addrs,
+                            pool: &this.pool,
                         };
                         this.behaviour.poll(cx, &mut parameters)
                     };
@@ -1359,6 +1557,24 @@ where
 // TODO: #[derive(Debug)]
 pub struct SwarmPollParameters<'a> {
     supported_protocols: &'a [Vec<u8>],
+    external_addresses: &'a HashMap<Multiaddr, ExternalAddrInfo>,
+    listened_addrs: &'a HashMap<ListenerId, SmallVec<[Multiaddr; 1]>>,
+    pool: &'a dyn ConnectedPeers,
+}
+
+/// Type-erased access to the connected peers of a [`Pool`], keeping [`SwarmPollParameters`]
+/// independent of the [`ConnectionHandler`] type.
+trait ConnectedPeers {
+    fn connected_peers(&self) -> Box<dyn Iterator<Item = &PeerId> + '_>;
+}
+
+impl<THandler> ConnectedPeers for Pool<THandler>
+where
+    THandler: ConnectionHandler,
+{
+    fn connected_peers(&self) -> Box<dyn Iterator<Item = &PeerId> + '_> {
+        Box::new(self.iter_connected())
+    }
 }
 
 impl<'a> PollParameters for SwarmPollParameters<'a> {
@@ -1367,6 +1583,18 @@ impl<'a> PollParameters for SwarmPollParameters<'a> {
     fn supported_protocols(&self) -> Self::SupportedProtocolsIter {
         self.supported_protocols.iter().cloned()
     }
+
+    fn external_addresses(&self) -> Box<dyn Iterator<Item = &Multiaddr> + '_> {
+        Box::new(self.external_addresses.keys())
+    }
+
+    fn listen_addresses(&self) -> Box<dyn Iterator<Item = &Multiaddr> + '_> {
+        Box::new(self.listened_addrs.values().flatten())
+    }
+
+    fn connected_peers(&self) -> Box<dyn Iterator<Item = &PeerId> + '_> {
+        self.pool.connected_peers()
+    }
 }
 
 pub struct Config {
@@ -1469,8 +1697,12 @@ impl Config {
     }
 
     /// The maximum number of inbound streams concurrently negotiating on a
-    /// connection. New inbound streams exceeding the limit are dropped and thus
-    /// reset.
+    /// connection (default is 128).
+    ///
+    /// Once the limit is reached, no further inbound streams are accepted from the
+    /// [`StreamMuxer`](libp2p_core::muxing::StreamMuxer) until a negotiation completes. New inbound
+    /// streams are thus queued by the stream muxer, which resets them once its own backlog is
+    /// full.
     ///
     /// Note: This only enforces a limit on the number of concurrently
     /// negotiating inbound streams. The total number of inbound streams on a
@@ -1657,8 +1889,12 @@ where
     }
 
     /// The maximum number of inbound streams concurrently negotiating on a
-    /// connection. New inbound streams exceeding the limit are dropped and thus
-    /// reset.
+    /// connection (default is 128).
+    ///
+    /// Once the limit is reached, no further inbound streams are accepted from the
+    /// [`StreamMuxer`](libp2p_core::muxing::StreamMuxer) until a negotiation completes. New inbound
+    /// streams are thus queued by the stream muxer, which resets them once its own backlog is
+    /// full.
     ///
     /// Note: This only enforces a limit on the number of concurrently
     /// negotiating inbound streams. The total number of inbound streams on a
@@ -1688,6 +1924,7 @@ where
             supported_protocols: Default::default(),
             confirmed_external_addr: Default::default(),
             listened_addrs: HashMap::new(),
+            peer_addresses: HashMap::new(),
             pending_event: None,
         }
     }
@@ -1937,6 +2174,57 @@ impl NetworkInfo {
     }
 }
 
+/// Information about the connections obtained by [`Swarm::network_info_extended()`].
+#[derive(Clone, Debug)]
+pub struct ExtendedNetworkInfo {
+    network_info: NetworkInfo,
+    /// The number of established connections per transport protocol.
+    connections_by_transport: HashMap<TransportProtocol, usize>,
+}
+
+impl ExtendedNetworkInfo {
+    /// The information also returned by [`Swarm::network_info()`].
+    pub fn network_info(&self) -> &NetworkInfo {
+        &self.network_info
+    }
+
+    /// The number of established connections per transport protocol.
+    ///
+    /// Relayed connections and connections over other transports are not included.
+    pub fn connections_by_transport(&self) -> &HashMap<TransportProtocol, usize> {
+        &self.connections_by_transport
+    }
+}
+
+/// The established connections to a peer, obtained by [`Swarm::peers()`].
+#[derive(Clone, Debug)]
+pub struct PeerState {
+    connections: Vec<(ConnectionId, ConnectedPoint)>,
+}
+
+impl PeerState {
+    /// The number of established connections to the peer.
+    pub fn num_connections(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// The established connections to the peer and their endpoints.
+    ///
+    /// Whether we dialed or accepted a connection is given by [`ConnectedPoint::to_endpoint`].
+    pub fn connections(&self) -> impl Iterator<Item = (ConnectionId, &ConnectedPoint)> {
+        self.connections
+            .iter()
+            .map(|(id, endpoint)| (*id, endpoint))
+    }
+
+    /// The remote addresses of the established connections to the peer.
+    pub fn remote_addresses(&self) -> impl Iterator<Item = &Multiaddr> {
+        self.connections
+            .iter()
+            .map(|(_, endpoint)| endpoint.get_remote_address())
+    }
+}
+
 /// Ensures a given `Multiaddr` is a `/p2p/...` address for the given peer.
 ///
 /// If the given address is already a `p2p` address for the given peer,
@@ -2048,6 +2336,76 @@ mod tests {
             && !swarm2.is_connected(swarm1.local_peer_id())
     }
 
+    /// Checks that the [`PollParameters`] passed to [`NetworkBehaviour::poll`] reflect the
+    /// listen addresses and connected peers of the [`Swarm`].
+    #[tokio::test]
+    async fn poll_parameters_reflect_swarm_state() {
+        let mut swarm1 = new_test_swarm(Config::with_tokio_executor());
+        let mut swarm2 = new_test_swarm(Config::with_tokio_executor());
+
+        let addr1: Multiaddr = multiaddr::Protocol::Memory(rand::random::<u64>()).into();
+        swarm1.listen_on(addr1.clone()).unwrap();
+        swarm2.dial(addr1.clone()).unwrap();
+
+        let swarm2_id = *swarm2.local_peer_id();
+
+        future::poll_fn(|cx| loop {
+            let poll1 = Swarm::poll_next_event(Pin::new(&mut swarm1), cx);
+            let poll2 = Swarm::poll_next_event(Pin::new(&mut swarm2), cx);
+
+            let behaviour = swarm1.behaviour();
+            if behaviour.poll_listen_addresses == [addr1.clone()]
+                && behaviour.poll_connected_peers == [swarm2_id]
+            {
+                return Poll::Ready(());
+            }
+
+            if poll1.is_pending() && poll2.is_pending() {
+                return Poll::Pending;
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn disconnect_peer_id_graceful_closes_idle_connections() {
+        let mut swarm1 = new_test_swarm(Config::with_tokio_executor());
+        let mut swarm2 = new_test_swarm(Config::with_tokio_executor());
+
+        let addr1: Multiaddr = multiaddr::Protocol::Memory(rand::random::<u64>()).into();
+        swarm1.listen_on(addr1.clone()).unwrap();
+        swarm2.dial(addr1).unwrap();
+
+        let swarm1_id = *swarm1.local_peer_id();
+        let mut disconnecting = false;
+
+        let cause = future::poll_fn(|cx| loop {
+            let poll1 = Swarm::poll_next_event(Pin::new(&mut swarm1), cx);
+            let poll2 = Swarm::poll_next_event(Pin::new(&mut swarm2), cx);
+
+            if !disconnecting && swarms_connected(&swarm1, &swarm2, 1) {
+                swarm2
+                    .disconnect_peer_id_graceful(swarm1_id, Duration::from_secs(10))
+                    .expect("Error disconnecting");
+                disconnecting = true;
+            }
+
+            if let Poll::Ready(SwarmEvent::ConnectionClosed { cause, .. }) = poll2 {
+                return Poll::Ready(cause);
+            }
+
+            if poll1.is_pending() && poll2.is_pending() {
+                return Poll::Pending;
+            }
+        });
+
+        // The connection is idle, hence it must close well before the idle timeout.
+        let cause = tokio::time::timeout(Duration::from_secs(1), cause)
+            .await
+            .expect("connection to be closed before the idle timeout");
+        assert!(cause.is_none(), "Unexpected cause: {cause:?}");
+    }
+
     /// Establishes multiple connections between two peers,
     /// after which one peer disconnects the other using [`Swarm::disconnect_peer_id`].
     ///
@@ -2443,6 +2801,133 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn external_addresses_are_ordered_by_confirmations() {
+        let mut swarm = new_test_swarm(Config::with_tokio_executor());
+        let once: Multiaddr = multiaddr::Protocol::Memory(1).into();
+        let twice: Multiaddr = multiaddr::Protocol::Memory(2).into();
+
+        swarm.add_external_address(once.clone());
+        swarm.add_external_address(twice.clone());
+        swarm.add_external_address(twice.clone());
+
+        let infos = swarm.external_addresses_with_info();
+        assert_eq!(
+            infos
+                .iter()
+                .map(|info| (info.address().clone(), info.confirmations()))
+                .collect::<Vec<_>>(),
+            vec![(twice.clone(), 2), (once, 1)]
+        );
+        assert!(infos[0].first_confirmed() <= infos[0].last_confirmed());
+
+        swarm.remove_external_address(&twice);
+        assert_eq!(swarm.external_addresses_with_info().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn listen_addrs_for_listener_are_concrete() {
+        let mut swarm = new_test_swarm(Config::with_tokio_executor());
+
+        let listener_id = swarm
+            .listen_on(multiaddr::Protocol::Memory(0).into())
+            .unwrap();
+        assert_eq!(swarm.listen_addrs_for_listener(listener_id).count(), 0);
+
+        let address = loop {
+            if let SwarmEvent::NewListenAddr {
+                listener_id: id,
+                address,
+            } = swarm.select_next_some().await
+            {
+                assert_eq!(id, listener_id);
+                break address;
+            }
+        };
+
+        assert_ne!(address, multiaddr::Protocol::Memory(0).into());
+        assert_eq!(
+            swarm
+                .listen_addrs_for_listener(listener_id)
+                .collect::<Vec<_>>(),
+            vec![&address]
+        );
+        assert_eq!(
+            swarm.listen_addrs_for_listener(ListenerId::next()).count(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn dial_peer_with_added_address() {
+        let mut swarm1 = new_test_swarm(Config::with_tokio_executor());
+        let mut swarm2 = new_test_swarm(Config::with_tokio_executor());
+
+        let addr1: Multiaddr = multiaddr::Protocol::Memory(rand::random::<u64>()).into();
+        swarm1.listen_on(addr1.clone()).unwrap();
+
+        let swarm1_id = *swarm1.local_peer_id();
+        assert!(matches!(
+            swarm2.dial(swarm1_id),
+            Err(DialError::NoAddresses)
+        ));
+
+        swarm2.add_peer_address(swarm1_id, addr1);
+        swarm2.dial(swarm1_id).unwrap();
+
+        future::poll_fn(|cx| loop {
+            let poll1 = Swarm::poll_next_event(Pin::new(&mut swarm1), cx);
+            let poll2 = Swarm::poll_next_event(Pin::new(&mut swarm2), cx);
+
+            if swarms_connected(&swarm1, &swarm2, 1) {
+                return Poll::Ready(());
+            }
+
+            if poll1.is_pending() && poll2.is_pending() {
+                return Poll::Pending;
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn peers_reports_established_connections() {
+        let mut swarm1 = new_test_swarm(Config::with_tokio_executor());
+        let mut swarm2 = new_test_swarm(Config::with_tokio_executor());
+
+        let addr1: Multiaddr = multiaddr::Protocol::Memory(rand::random::<u64>()).into();
+        swarm1.listen_on(addr1.clone()).unwrap();
+        swarm2.dial(addr1.clone()).unwrap();
+
+        future::poll_fn(|cx| loop {
+            let poll1 = Swarm::poll_next_event(Pin::new(&mut swarm1), cx);
+            let poll2 = Swarm::poll_next_event(Pin::new(&mut swarm2), cx);
+
+            if swarms_connected(&swarm1, &swarm2, 1) {
+                return Poll::Ready(());
+            }
+
+            if poll1.is_pending() && poll2.is_pending() {
+                return Poll::Pending;
+            }
+        })
+        .await;
+
+        let peers = swarm2.peers().collect::<Vec<_>>();
+        assert_eq!(peers.len(), 1);
+        let (peer, state) = &peers[0];
+        assert_eq!(*peer, swarm1.local_peer_id());
+        assert_eq!(state.num_connections(), 1);
+        assert_eq!(state.remote_addresses().collect::<Vec<_>>(), vec![&addr1]);
+        assert!(state
+            .connections()
+            .all(|(_, endpoint)| endpoint.to_endpoint() == Endpoint::Dialer));
+
+        assert!(swarm1
+            .peers()
+            .all(|(_, state)| state.connections().all(|(_, e)| e.is_listener())));
+    }
+
     #[tokio::test]
     async fn dial_self_by_id() {
         // Trying to dial self by passing the same `PeerId` shouldn't even be possible in the first
@@ -2551,4 +3036,30 @@ mod tests {
         // Unfortunately, we have some "empty" errors that lead to multiple colons without text but that is the best we can do.
         assert_eq!("Failed to negotiate transport protocol(s): [(/ip4/127.0.0.1/tcp/80: : No listener on the given port.)]", string)
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn connection_established_serializes_to_json() {
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/80".parse().unwrap();
+        let event = SwarmEvent::<(), io::Error>::ConnectionEstablished {
+            peer_id: PeerId::random(),
+            connection_id: ConnectionId::new_unchecked(1),
+            endpoint: ConnectedPoint::Dialer {
+                address: address.clone(),
+                role_override: Endpoint::Dialer,
+            },
+            num_established: NonZeroU32::new(1).unwrap(),
+            concurrent_dial_errors: Some(vec![(
+                address,
+                TransportError::Other(io::Error::new(io::ErrorKind::Other, "unreachable")),
+            )]),
+            established_in: Duration::from_secs(1),
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+
+        assert!(json.contains("ConnectionEstablished"));
+        assert!(json.contains("/ip4/127.0.0.1/tcp/80"));
+        assert!(json.contains("unreachable"));
+    }
 }
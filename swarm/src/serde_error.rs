@@ -0,0 +1,78 @@
+//! Serialization of the error types contained in a [`SwarmEvent`](crate::SwarmEvent).
+//!
+//! Errors don't implement [`serde::Serialize`], hence they are serialized as their
+//! [`Display`](fmt::Display) representation.
+
+use libp2p_core::{transport::TransportError, Multiaddr};
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+use std::{error::Error, fmt, io};
+
+pub(crate) fn display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+pub(crate) fn option_display<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display,
+    S: Serializer,
+{
+    value
+        .as_ref()
+        .map(ToString::to_string)
+        .serialize(serializer)
+}
+
+pub(crate) fn result_display<T, E, S>(
+    value: &Result<T, E>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    E: fmt::Display,
+    S: Serializer,
+{
+    value
+        .as_ref()
+        .map_err(ToString::to_string)
+        .serialize(serializer)
+}
+
+pub(crate) fn dial_errors<S>(
+    value: &Option<Vec<(Multiaddr, TransportError<io::Error>)>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(errors) => serializer.serialize_some(&DialErrors(errors)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes dial errors as a sequence of addresses and error messages.
+struct DialErrors<'a>(&'a [(Multiaddr, TransportError<io::Error>)]);
+
+impl Serialize for DialErrors<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for (address, error) in self.0 {
+            // `TransportError::Other` doesn't display its source, hence include the error chain.
+            let mut message = error.to_string();
+            let mut source = error.source();
+            while let Some(error) = source {
+                message.push_str(&format!(": {error}"));
+                source = error.source();
+            }
+            seq.serialize_element(&(address, message))?;
+        }
+        seq.end()
+    }
+}
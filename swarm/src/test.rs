@@ -173,6 +173,10 @@ where
     pub(crate) on_listener_error: Vec<ListenerId>,
     pub(crate) on_listener_closed: Vec<(ListenerId, bool)>,
     pub(crate) poll: usize,
+    /// The listen addresses reported by the [`PollParameters`] of the last `poll`.
+    pub(crate) poll_listen_addresses: Vec<Multiaddr>,
+    /// The connected peers reported by the [`PollParameters`] of the last `poll`.
+    pub(crate) poll_connected_peers: Vec<PeerId>,
 }
 
 impl<TInner> CallTraceBehaviour<TInner>
@@ -199,6 +203,8 @@ where
             on_listener_error: Vec::new(),
             on_listener_closed: Vec::new(),
             poll: 0,
+            poll_listen_addresses: Vec::new(),
+            poll_connected_peers: Vec::new(),
         }
     }
 
@@ -563,6 +569,8 @@ where
         args: &mut impl PollParameters,
     ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
         self.poll += 1;
+        self.poll_listen_addresses = args.listen_addresses().cloned().collect();
+        self.poll_connected_peers = args.connected_peers().copied().collect();
         self.inner.poll(cx, args)
     }
 }
@@ -0,0 +1,73 @@
+use libp2p_core::multiaddr::{Multiaddr, Protocol};
+
+/// The transport protocol of a [`Multiaddr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransportProtocol {
+    /// Plain TCP, i.e. `/tcp`.
+    Tcp,
+    /// QUIC, i.e. `/quic` or `/quic-v1`.
+    Quic,
+    /// WebSocket, either on top of TCP or TLS, i.e. `/ws` or `/wss`.
+    Ws,
+    /// WebRTC direct, i.e. `/webrtc-direct`.
+    Webrtc,
+    /// WebTransport on top of QUIC, i.e. `/webtransport`.
+    WebTransport,
+}
+
+impl TransportProtocol {
+    /// Determines the transport protocol of the given address from its outermost transport
+    /// component, e.g. `/ip4/127.0.0.1/tcp/4001/ws` is [`TransportProtocol::Ws`].
+    ///
+    /// Returns `None` for relayed addresses and addresses of other transports.
+    pub fn from_multiaddr(addr: &Multiaddr) -> Option<Self> {
+        let mut transport = None;
+
+        for protocol in addr.iter() {
+            transport = match protocol {
+                Protocol::Tcp(_) => Some(TransportProtocol::Tcp),
+                Protocol::Quic | Protocol::QuicV1 => Some(TransportProtocol::Quic),
+                Protocol::Ws(_) | Protocol::Wss(_) => Some(TransportProtocol::Ws),
+                Protocol::WebRTCDirect | Protocol::P2pWebRtcDirect => {
+                    Some(TransportProtocol::Webrtc)
+                }
+                Protocol::WebTransport => Some(TransportProtocol::WebTransport),
+                Protocol::P2pCircuit => return None,
+                _ => continue,
+            };
+        }
+
+        transport
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outermost_transport_is_returned() {
+        for (addr, expected) in [
+            ("/ip4/127.0.0.1/tcp/4001", Some(TransportProtocol::Tcp)),
+            ("/ip6/::1/tcp/4001/ws", Some(TransportProtocol::Ws)),
+            ("/dns/example.com/tcp/443/wss", Some(TransportProtocol::Ws)),
+            ("/ip4/127.0.0.1/udp/4001/quic-v1", Some(TransportProtocol::Quic)),
+            (
+                "/ip4/127.0.0.1/udp/4001/quic-v1/webtransport",
+                Some(TransportProtocol::WebTransport),
+            ),
+            (
+                "/ip4/127.0.0.1/udp/4001/webrtc-direct",
+                Some(TransportProtocol::Webrtc),
+            ),
+            ("/memory/1234", None),
+            (
+                "/ip4/127.0.0.1/tcp/4001/p2p/12D3KooWDpJ7As7BWAwRMfu1VU2WCqNjvq387JEYKDBj4kx6nXTN/p2p-circuit",
+                None,
+            ),
+        ] {
+            let addr = addr.parse::<Multiaddr>().unwrap();
+            assert_eq!(TransportProtocol::from_multiaddr(&addr), expected, "{addr}");
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use libp2p_ping as ping;
+use libp2p_swarm::Swarm;
+use libp2p_swarm_test::SwarmExt;
+
+#[async_std::test]
+async fn counts_open_streams() {
+    let mut swarm1 = Swarm::new_ephemeral(|_| ping::Behaviour::default());
+    let mut swarm2 = Swarm::new_ephemeral(|_| ping::Behaviour::default());
+
+    swarm1.listen().await;
+    swarm2.connect(&mut swarm1).await;
+
+    let ([_], [_]): ([ping::Event; 1], [ping::Event; 1]) =
+        libp2p_swarm_test::drive(&mut swarm1, &mut swarm2).await;
+
+    // Ping keeps its outbound stream and the inbound stream of the remote open between pings.
+    let peer1 = *swarm1.local_peer_id();
+    let peer2 = *swarm2.local_peer_id();
+    assert_eq!(swarm1.num_substreams(&peer2), 2);
+    assert_eq!(swarm2.num_substreams(&peer1), 2);
+    assert_eq!(swarm1.total_substreams(), 2);
+    assert_eq!(swarm1.num_substreams(&peer1), 0);
+
+    drop(swarm2);
+    swarm1
+        .wait(|e| match e {
+            libp2p_swarm::SwarmEvent::ConnectionClosed { .. } => Some(()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(swarm1.total_substreams(), 0);
+}
@@ -37,12 +37,18 @@ use std::{
 /// A noise session to a remote.
 ///
 /// `T` is the type of the underlying I/O resource.
+///
+/// Frames are only read from the underlying I/O resource when the application reads from the
+/// session, and at most one frame of up to 65535 bytes is buffered at a time. A remote sending
+/// faster than the application consumes the data is thus held back by the flow control of the
+/// underlying I/O resource instead of filling memory.
 pub struct Output<T> {
     io: NoiseFramed<T, snow::TransportState>,
     recv_buffer: Bytes,
     recv_offset: usize,
     send_buffer: Vec<u8>,
     send_offset: usize,
+    handshake_hash: [u8; 32],
 }
 
 impl<T> fmt::Debug for Output<T> {
@@ -52,15 +58,24 @@ impl<T> fmt::Debug for Output<T> {
 }
 
 impl<T> Output<T> {
-    fn new(io: NoiseFramed<T, snow::TransportState>) -> Self {
+    fn new(io: NoiseFramed<T, snow::TransportState>, handshake_hash: [u8; 32]) -> Self {
         Output {
             io,
             recv_buffer: Bytes::new(),
             recv_offset: 0,
             send_buffer: Vec::new(),
             send_offset: 0,
+            handshake_hash,
         }
     }
+
+    /// The hash of the completed handshake.
+    ///
+    /// Both sides of a session compute the same hash and it is unique to the session, thus it
+    /// can be used as a channel binding by protocols on top of the session.
+    pub fn handshake_hash(&self) -> [u8; 32] {
+        self.handshake_hash
+    }
 }
 
 impl<T: AsyncRead + Unpin> AsyncRead for Output<T> {
@@ -106,6 +106,11 @@ impl<T> NoiseFramed<T, snow::HandshakeState> {
         })?;
 
         let dh_remote_pubkey = PublicKey::from_slice(dh_remote_pubkey)?;
+        let handshake_hash = self
+            .session
+            .get_handshake_hash()
+            .try_into()
+            .expect("SHA256 handshake hash to be 32 bytes");
 
         let io = NoiseFramed {
             session: self.session.into_transport_mode()?,
@@ -117,7 +122,7 @@ impl<T> NoiseFramed<T, snow::HandshakeState> {
             decrypt_buffer: self.decrypt_buffer,
         };
 
-        Ok((dh_remote_pubkey, Output::new(io)))
+        Ok((dh_remote_pubkey, Output::new(io, handshake_hash)))
     }
 }
 
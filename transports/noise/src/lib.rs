@@ -64,7 +64,9 @@ pub use io::Output;
 use crate::handshake::State;
 use crate::io::handshake;
 use crate::protocol::{noise_params_into_builder, AuthenticKeypair, Keypair, PARAMS_XX};
+use futures::future::{self, Either};
 use futures::prelude::*;
+use futures_timer::Delay;
 use libp2p_core::{InboundUpgrade, OutboundUpgrade, UpgradeInfo};
 use libp2p_identity as identity;
 use libp2p_identity::PeerId;
@@ -74,6 +76,7 @@ use snow::params::NoiseParams;
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::pin::Pin;
+use std::time::Duration;
 
 /// The configuration for the noise handshake.
 #[derive(Clone)]
@@ -89,21 +92,34 @@ pub struct Config {
     ///
     /// For further information, see <https://noiseprotocol.org/noise.html#prologue>.
     prologue: Vec<u8>,
+
+    /// Maximum duration of the handshake before it is aborted with [`Error::Timeout`].
+    handshake_timeout: Duration,
 }
 
 impl Config {
     /// Construct a new configuration for the noise handshake using the XX handshake pattern.
     pub fn new(identity: &identity::Keypair) -> Result<Self, Error> {
-        let noise_keys = Keypair::new().into_authentic(identity)?;
+        let noise_keys = Keypair::from_identity(identity).into_authentic(identity)?;
 
         Ok(Self {
             dh_keys: noise_keys,
             params: PARAMS_XX.clone(),
             webtransport_certhashes: None,
             prologue: vec![],
+            handshake_timeout: Duration::from_secs(20),
         })
     }
 
+    /// The X25519 public key used as static key in the noise handshake.
+    ///
+    /// The key is derived from the identity keypair passed to [`Config::new`] and is thus the
+    /// same every time, e.g. across restarts. RSA identities are the exception, a new static
+    /// key is generated for each [`Config`] created from them.
+    pub fn static_public_key(&self) -> [u8; 32] {
+        self.dh_keys.keypair.public().to_bytes()
+    }
+
     /// Set the noise prologue.
     pub fn with_prologue(mut self, prologue: Vec<u8>) -> Self {
         self.prologue = prologue;
@@ -121,6 +137,15 @@ impl Config {
         self
     }
 
+    /// Set the maximum duration of the handshake.
+    ///
+    /// A remote that stalls in the middle of the handshake is disconnected once this duration
+    /// has elapsed. Defaults to 20 seconds.
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
     fn into_responder<S>(self, socket: S) -> Result<State<S>, Error> {
         let session = noise_params_into_builder(
             self.params,
@@ -180,7 +205,8 @@ where
     type Future = Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send>>;
 
     fn upgrade_inbound(self, socket: T, _: Self::Info) -> Self::Future {
-        async move {
+        let timeout = self.handshake_timeout;
+        let handshake = async move {
             let mut state = self.into_responder(socket)?;
 
             handshake::recv_empty(&mut state).await?;
@@ -190,8 +216,9 @@ where
             let (pk, io) = state.finish()?;
 
             Ok((pk.to_peer_id(), io))
-        }
-        .boxed()
+        };
+
+        with_timeout(handshake, timeout).boxed()
     }
 }
 
@@ -204,7 +231,8 @@ where
     type Future = Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send>>;
 
     fn upgrade_outbound(self, socket: T, _: Self::Info) -> Self::Future {
-        async move {
+        let timeout = self.handshake_timeout;
+        let handshake = async move {
             let mut state = self.into_initiator(socket)?;
 
             handshake::send_empty(&mut state).await?;
@@ -214,8 +242,25 @@ where
             let (pk, io) = state.finish()?;
 
             Ok((pk.to_peer_id(), io))
+        };
+
+        with_timeout(handshake, timeout).boxed()
+    }
+}
+
+/// Fails the given handshake with [`Error::Timeout`] if it does not complete within `timeout`.
+async fn with_timeout<O>(
+    handshake: impl Future<Output = Result<O, Error>>,
+    timeout: Duration,
+) -> Result<O, Error> {
+    futures::pin_mut!(handshake);
+
+    match future::select(handshake, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(((), _)) => {
+            log::warn!("Noise handshake did not complete within {timeout:?}");
+            Err(Error::Timeout)
         }
-        .boxed()
     }
 }
 
@@ -243,6 +288,8 @@ pub enum Error {
     SigningError(#[from] libp2p_identity::SigningError),
     #[error("Expected WebTransport certhashes ({}) are not a subset of received ones ({})", certhashes_to_string(.0), certhashes_to_string(.1))]
     UnknownWebTransportCerthashes(HashSet<Multihash<64>>, HashSet<Multihash<64>>),
+    #[error("Handshake timed out")]
+    Timeout,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -111,6 +111,26 @@ impl Keypair {
         }
     }
 
+    /// The public key of the DH keypair.
+    pub(crate) fn public(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Derive a X25519 keypair from the given identity keypair.
+    ///
+    /// The same identity keypair always results in the same X25519 keypair. As secrets cannot
+    /// be derived from RSA keys, a new random X25519 keypair is created for those.
+    pub(crate) fn from_identity(id_keys: &identity::Keypair) -> Keypair {
+        match id_keys.derive_secret(b"libp2p noise static key") {
+            Some(mut sk_bytes) => {
+                let sk = SecretKey(sk_bytes); // Copy
+                sk_bytes.zeroize();
+                Self::from(sk)
+            }
+            None => Self::new(),
+        }
+    }
+
     /// Create a new X25519 keypair.
     pub(crate) fn new() -> Keypair {
         let mut sk_bytes = [0u8; 32];
@@ -151,6 +171,10 @@ impl PublicKey {
         key.copy_from_slice(slice);
         Ok(PublicKey(key))
     }
+
+    pub(crate) fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
 }
 
 impl AsRef<[u8]> for PublicKey {
@@ -25,7 +25,7 @@ use libp2p_identity as identity;
 use libp2p_noise as noise;
 use log::info;
 use quickcheck::*;
-use std::{convert::TryInto, io};
+use std::{convert::TryInto, io, time::Duration};
 
 #[allow(dead_code)]
 fn core_upgrade_compat() {
@@ -105,6 +105,71 @@ fn xx() {
         .quickcheck(prop as fn(Vec<Message>) -> bool)
 }
 
+#[test]
+fn stalled_handshake_times_out() {
+    let _ = env_logger::try_init();
+    let server_id = identity::Keypair::generate_ed25519();
+
+    // The client never sends anything.
+    let (_client, server) = futures_ringbuf::Endpoint::pair(100, 100);
+
+    let result = futures::executor::block_on(
+        noise::Config::new(&server_id)
+            .unwrap()
+            .with_handshake_timeout(Duration::from_millis(100))
+            .upgrade_inbound(server, ""),
+    );
+
+    assert!(matches!(result, Err(noise::Error::Timeout)));
+}
+
+#[test]
+fn handshake_hash_is_shared_and_unique() {
+    let handshake = || async {
+        let (client, server) = futures_ringbuf::Endpoint::pair(100, 100);
+        let ((_, server_session), (_, client_session)) = futures::future::try_join(
+            noise::Config::new(&identity::Keypair::generate_ed25519())
+                .unwrap()
+                .upgrade_inbound(server, ""),
+            noise::Config::new(&identity::Keypair::generate_ed25519())
+                .unwrap()
+                .upgrade_outbound(client, ""),
+        )
+        .await
+        .unwrap();
+
+        (
+            server_session.handshake_hash(),
+            client_session.handshake_hash(),
+        )
+    };
+
+    futures::executor::block_on(async {
+        let (server_hash, client_hash) = handshake().await;
+        assert_eq!(server_hash, client_hash);
+
+        let (other_hash, _) = handshake().await;
+        assert_ne!(server_hash, other_hash);
+    });
+}
+
+#[test]
+fn static_key_is_derived_from_identity() {
+    let id = identity::Keypair::generate_ed25519();
+    let other_id = identity::Keypair::generate_ed25519();
+
+    let static_key = noise::Config::new(&id).unwrap().static_public_key();
+
+    assert_eq!(
+        static_key,
+        noise::Config::new(&id).unwrap().static_public_key()
+    );
+    assert_ne!(
+        static_key,
+        noise::Config::new(&other_id).unwrap().static_public_key()
+    );
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Message(Vec<u8>);
 
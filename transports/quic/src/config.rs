@@ -28,6 +28,10 @@ pub struct Config {
     /// The actual timeout is the minimum of this and the [`Config::max_idle_timeout`].
     pub handshake_timeout: Duration,
     /// Maximum duration of inactivity in ms to accept before timing out the connection.
+    ///
+    /// This is applied to the [`quinn::TransportConfig`] and is independent of the
+    /// idle connection timeout of the `Swarm`, which only closes connections that
+    /// no handler wants to keep alive. See [`quinn::TransportConfig::max_idle_timeout`].
     pub max_idle_timeout: u32,
     /// Period of inactivity before sending a keep-alive packet.
     /// Must be set lower than the idle_timeout of both
@@ -57,23 +61,55 @@ pub struct Config {
     /// As client the version is chosen based on the remote's address.
     pub support_draft_29: bool,
 
+    /// Lifetime of the connection IDs we issue to remotes.
+    ///
+    /// QUIC connection IDs are visible to on-path observers. When set, every connection ID is
+    /// retired after this interval and the remote is asked to switch to a new one, which makes
+    /// it harder to link the packets of a long-lived connection.
+    ///
+    /// Per default connection IDs are not rotated.
+    pub connection_id_rotation_interval: Option<Duration>,
+
     /// TLS client config for the inner [`quinn::ClientConfig`].
     client_tls_config: Arc<rustls::ClientConfig>,
     /// TLS server config for the inner [`quinn::ServerConfig`].
     server_tls_config: Arc<rustls::ServerConfig>,
+    /// SHA-256 fingerprint of the TLS certificate used in both `client_tls_config` and
+    /// `server_tls_config`.
+    certificate_fingerprint: [u8; 32],
     /// Libp2p identity of the node.
     keypair: libp2p_identity::Keypair,
+    /// User-provided [`quinn::ServerConfig`], replacing the one derived from `server_tls_config`.
+    server_config: Option<quinn::ServerConfig>,
+    /// User-provided [`quinn::ClientConfig`], replacing the one derived from `client_tls_config`.
+    client_config: Option<quinn::ClientConfig>,
 }
 
 impl Config {
     /// Creates a new configuration object with default values.
     pub fn new(keypair: &libp2p_identity::Keypair) -> Self {
-        let client_tls_config = Arc::new(libp2p_tls::make_client_config(keypair, None).unwrap());
-        let server_tls_config = Arc::new(libp2p_tls::make_server_config(keypair).unwrap());
+        let (certificate, private_key) = libp2p_tls::certificate::generate(keypair).unwrap();
+        let certificate_fingerprint = ring::digest::digest(&ring::digest::SHA256, &certificate.0)
+            .as_ref()
+            .try_into()
+            .expect("SHA-256 digest to be 32 bytes");
+        let client_tls_config = Arc::new(
+            libp2p_tls::make_client_config_with_certificate(
+                certificate.clone(),
+                private_key.clone(),
+                None,
+            )
+            .unwrap(),
+        );
+        let server_tls_config = Arc::new(
+            libp2p_tls::make_server_config_with_certificate(certificate, private_key).unwrap(),
+        );
         Self {
             client_tls_config,
             server_tls_config,
+            certificate_fingerprint,
             support_draft_29: false,
+            connection_id_rotation_interval: None,
             handshake_timeout: Duration::from_secs(5),
             max_idle_timeout: 30 * 1000,
             max_concurrent_stream_limit: 256,
@@ -83,8 +119,61 @@ impl Config {
             // Ensure that one stream is not consuming the whole connection.
             max_stream_data: 10_000_000,
             keypair: keypair.clone(),
+            server_config: None,
+            client_config: None,
         }
     }
+
+    /// The SHA-256 fingerprint of the TLS certificate presented to remotes.
+    ///
+    /// The certificate is generated in [`Config::new`] and used for both inbound and outbound
+    /// connections, unless replaced through [`Config::with_server_config`] or
+    /// [`Config::with_client_config`].
+    pub fn certificate_fingerprint(&self) -> [u8; 32] {
+        self.certificate_fingerprint
+    }
+
+    /// Use the given [`quinn::ServerConfig`] for inbound connections instead of the one derived
+    /// from the node's [`Keypair`](libp2p_identity::Keypair), e.g. to sign the TLS handshake with
+    /// a key held in an HSM.
+    ///
+    /// The transport settings of this [`Config`] are still applied on top of the given config.
+    ///
+    /// # Security
+    ///
+    /// The given config **must** verify the certificate of the remote as mandated by the
+    /// [libp2p TLS spec](https://github.com/libp2p/specs/blob/master/tls/tls.md), including that
+    /// the remote proves possession of the certificate key during the handshake. The verifier
+    /// inside the config cannot be wrapped, hence the transport only parses the remote's libp2p
+    /// certificate after the handshake. That does not prove that the remote owns the
+    /// certificate, i.e. a config with a permissive verifier allows a remote to replay the
+    /// certificate of another node and impersonate its [`PeerId`](libp2p_identity::PeerId).
+    ///
+    /// Build the underlying TLS config with
+    /// [`libp2p_tls::make_server_config_with_certificate`] to keep the libp2p verifier.
+    pub fn with_server_config(mut self, server_config: quinn::ServerConfig) -> Self {
+        self.server_config = Some(server_config);
+        self
+    }
+
+    /// Use the given [`quinn::ClientConfig`] for outbound connections instead of the one derived
+    /// from the node's [`Keypair`](libp2p_identity::Keypair).
+    ///
+    /// The transport settings of this [`Config`] are still applied on top of the given config.
+    ///
+    /// # Security
+    ///
+    /// The given config **must** verify the certificate of the remote as mandated by the
+    /// [libp2p TLS spec](https://github.com/libp2p/specs/blob/master/tls/tls.md), see
+    /// [`Config::with_server_config`]. Otherwise a remote can impersonate the
+    /// [`PeerId`](libp2p_identity::PeerId) of another node.
+    ///
+    /// Build the underlying TLS config with
+    /// [`libp2p_tls::make_client_config_with_certificate`] to keep the libp2p verifier.
+    pub fn with_client_config(mut self, client_config: quinn::ClientConfig) -> Self {
+        self.client_config = Some(client_config);
+        self
+    }
 }
 
 /// Represents the inner configuration for [`quinn`].
@@ -106,8 +195,12 @@ impl From<Config> for QuinnConfig {
             max_connection_data,
             max_stream_data,
             support_draft_29,
+            connection_id_rotation_interval,
             handshake_timeout: _,
+            certificate_fingerprint: _,
             keypair,
+            server_config,
+            client_config,
         } = config;
         let mut transport = quinn::TransportConfig::default();
         // Disable uni-directional streams.
@@ -122,14 +215,16 @@ impl From<Config> for QuinnConfig {
         transport.receive_window(max_connection_data.into());
         let transport = Arc::new(transport);
 
-        let mut server_config = quinn::ServerConfig::with_crypto(server_tls_config);
+        let mut server_config =
+            server_config.unwrap_or_else(|| quinn::ServerConfig::with_crypto(server_tls_config));
         server_config.transport = Arc::clone(&transport);
         // Disables connection migration.
         // Long-term this should be enabled, however we then need to handle address change
         // on connections in the `Connection`.
         server_config.migration(false);
 
-        let mut client_config = quinn::ClientConfig::new(client_tls_config);
+        let mut client_config =
+            client_config.unwrap_or_else(|| quinn::ClientConfig::new(client_tls_config));
         client_config.transport_config(transport);
 
         let mut endpoint_config = keypair
@@ -144,6 +239,14 @@ impl From<Config> for QuinnConfig {
             endpoint_config.supported_versions(vec![1]);
         }
 
+        if let Some(lifetime) = connection_id_rotation_interval {
+            endpoint_config.cid_generator(move || {
+                let mut generator = quinn_proto::RandomConnectionIdGenerator::default();
+                generator.set_lifetime(lifetime);
+                Box::new(generator)
+            });
+        }
+
         QuinnConfig {
             client_config,
             server_config,
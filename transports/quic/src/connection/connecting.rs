@@ -29,6 +29,7 @@ use futures::{
 use futures_timer::Delay;
 use libp2p_identity::PeerId;
 use std::{
+    io,
     pin::Pin,
     task::{Context, Poll},
     time::Duration,
@@ -49,20 +50,29 @@ impl Connecting {
 }
 
 impl Connecting {
-    /// Returns the address of the node we're connected to.
-    /// Panics if the connection is still handshaking.
-    fn remote_peer_id(connection: &quinn::Connection) -> PeerId {
-        let identity = connection
+    /// Returns the [`PeerId`] of the node we're connected to.
+    ///
+    /// Fails if the remote did not present a valid libp2p certificate, which is only possible if
+    /// the TLS configuration was provided by the user. Note that this does not check that the
+    /// remote owns the certificate, which is up to the verifier of the TLS configuration.
+    fn remote_peer_id(connection: &quinn::Connection) -> Result<PeerId, Error> {
+        let invalid_certificate = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "remote has no libp2p certificate",
+            )
+        };
+
+        let certificates: Box<Vec<rustls::Certificate>> = connection
             .peer_identity()
-            .expect("connection got identity because it passed TLS handshake; qed");
-        let certificates: Box<Vec<rustls::Certificate>> =
-            identity.downcast().expect("we rely on rustls feature; qed");
-        let end_entity = certificates
-            .get(0)
-            .expect("there should be exactly one certificate; qed");
+            .ok_or_else(invalid_certificate)?
+            .downcast()
+            .map_err(|_| invalid_certificate())?;
+        let end_entity = certificates.get(0).ok_or_else(invalid_certificate)?;
         let p2p_cert = libp2p_tls::certificate::parse(end_entity)
-            .expect("the certificate was validated during TLS handshake; qed");
-        p2p_cert.peer_id()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(p2p_cert.peer_id())
     }
 }
 
@@ -75,7 +85,13 @@ impl Future for Connecting {
             Either::Left((connection, _)) => connection.map_err(ConnectionError)?,
         };
 
-        let peer_id = Self::remote_peer_id(&connection);
+        let peer_id = match Self::remote_peer_id(&connection) {
+            Ok(peer_id) => peer_id,
+            Err(e) => {
+                connection.close(0u32.into(), b"invalid certificate");
+                return Poll::Ready(Err(e));
+            }
+        };
         let muxer = Connection::new(connection);
         Poll::Ready(Ok((peer_id, muxer)))
     }
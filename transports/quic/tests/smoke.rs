@@ -64,6 +64,97 @@ async fn endpoint_reuse() {
     assert_eq!(a_send_back_addr, a_addr);
 }
 
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn custom_server_config_without_client_auth() {
+    let _ = env_logger::try_init();
+
+    let keypair = generate_tls_keypair();
+    let (certificate, private_key) = libp2p_tls::certificate::generate(&keypair).unwrap();
+    let mut crypto = rustls::ServerConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(vec![certificate], private_key)
+        .unwrap();
+    crypto.alpn_protocols = vec![b"libp2p".to_vec()];
+    let config = quic::Config::new(&keypair)
+        .with_server_config(quinn::ServerConfig::with_crypto(Arc::new(crypto)));
+    let mut a_transport = quic::GenTransport::<quic::tokio::Provider>::new(config)
+        .map(|(p, c), _| (p, StreamMuxerBox::new(c)))
+        .boxed();
+    let (_, mut b_transport) = create_default_transport::<quic::tokio::Provider>();
+
+    let a_addr = start_listening(&mut a_transport, "/ip4/127.0.0.1/udp/0/quic-v1").await;
+    let (listener_result, dialer_result) = future::join(
+        async {
+            let (upgrade, _) = a_transport
+                .select_next_some()
+                .await
+                .into_incoming()
+                .unwrap();
+            upgrade.await
+        },
+        b_transport.dial(a_addr).unwrap(),
+    )
+    .await;
+
+    // The dialer verified the listener's libp2p certificate, but the listener never asked for
+    // the dialer's certificate and must thus reject the connection.
+    assert_eq!(dialer_result.unwrap().0, keypair.public().to_peer_id());
+    assert_eq!(
+        listener_result.unwrap_err().to_string(),
+        "remote has no libp2p certificate"
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn idle_connection_times_out_without_keep_alive() {
+    let _ = env_logger::try_init();
+    let with_config = |config: &mut quic::Config| {
+        config.max_idle_timeout = 300;
+        config.keep_alive_interval = Duration::from_secs(10);
+    };
+    let (_, mut a_transport) = create_transport::<quic::tokio::Provider>(with_config);
+    let (_, mut b_transport) = create_transport::<quic::tokio::Provider>(with_config);
+
+    let a_addr = start_listening(&mut a_transport, "/ip4/127.0.0.1/udp/0/quic-v1").await;
+    let ((_, _, _a_connection), (_, mut b_connection)) =
+        connect(&mut a_transport, &mut b_transport, a_addr).await;
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    match poll_fn(|cx| b_connection.poll_outbound_unpin(cx)).await {
+        Err(error) => assert!(error.to_string().contains("timed out"), "{error}"),
+        Ok(_) => panic!("Expected the idle connection to be closed"),
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn keep_alive_prevents_idle_timeout() {
+    let _ = env_logger::try_init();
+    let with_config = |config: &mut quic::Config| {
+        config.max_idle_timeout = 300;
+        config.keep_alive_interval = Duration::from_millis(100);
+    };
+    let (_, mut a_transport) = create_transport::<quic::tokio::Provider>(with_config);
+    let (_, mut b_transport) = create_transport::<quic::tokio::Provider>(with_config);
+
+    let a_addr = start_listening(&mut a_transport, "/ip4/127.0.0.1/udp/0/quic-v1").await;
+    let ((_, _, _a_connection), (_, mut b_connection)) =
+        connect(&mut a_transport, &mut b_transport, a_addr).await;
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    poll_fn(|cx| b_connection.poll_outbound_unpin(cx))
+        .await
+        .expect("keep-alive to keep the connection open");
+}
+
 #[cfg(feature = "async-std")]
 #[async_std::test]
 async fn ipv4_dial_ipv6() {
@@ -363,6 +454,27 @@ async fn backpressure() {
     assert!(stream_a.write(&more_data).now_or_never().is_some());
 }
 
+#[cfg(feature = "async-std")]
+#[async_std::test]
+async fn connection_id_rotation() {
+    let _ = env_logger::try_init();
+    let (mut stream_a, mut stream_b) =
+        build_streams_with_config::<quic::async_std::Provider>(|config| {
+            config.connection_id_rotation_interval = Some(Duration::from_millis(50));
+        })
+        .await;
+
+    // Let several connection IDs expire while the connection is in use.
+    for i in 0..10u8 {
+        futures_timer::Delay::new(Duration::from_millis(50)).await;
+
+        stream_a.write_all(&[i]).await.unwrap();
+        let mut buf = [0];
+        stream_b.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [i]);
+    }
+}
+
 #[cfg(feature = "async-std")]
 #[async_std::test]
 async fn read_after_peer_dropped_stream() {
@@ -454,8 +566,14 @@ async fn smoke<P: Provider>() {
 }
 
 async fn build_streams<P: Provider + Spawn>() -> (SubstreamBox, SubstreamBox) {
-    let (_, mut a_transport) = create_default_transport::<P>();
-    let (_, mut b_transport) = create_default_transport::<P>();
+    build_streams_with_config::<P>(|_| {}).await
+}
+
+async fn build_streams_with_config<P: Provider + Spawn>(
+    with_config: impl Fn(&mut quic::Config),
+) -> (SubstreamBox, SubstreamBox) {
+    let (_, mut a_transport) = create_transport::<P>(&with_config);
+    let (_, mut b_transport) = create_transport::<P>(&with_config);
 
     let addr = start_listening(&mut a_transport, "/ip4/127.0.0.1/udp/0/quic-v1").await;
     let ((_, _, mut conn_a), (_, mut conn_b)) =
@@ -71,6 +71,8 @@ pub struct Config {
     backlog: u32,
     /// Whether port reuse should be enabled.
     enable_port_reuse: bool,
+    /// Timeout for establishing outgoing connections.
+    connect_timeout: Duration,
 }
 
 type Port = u16;
@@ -162,12 +164,15 @@ impl Config {
     ///     See [`Config::ttl`].
     ///   * The size of the listen backlog for new listening sockets is `1024`.
     ///     See [`Config::listen_backlog`].
+    ///   * Outgoing connections that are not established within 10 seconds fail.
+    ///     See [`Config::connect_timeout`].
     pub fn new() -> Self {
         Self {
             ttl: None,
             nodelay: None,
             backlog: 1024,
             enable_port_reuse: false,
+            connect_timeout: Duration::from_secs(10),
         }
     }
 
@@ -189,6 +194,15 @@ impl Config {
         self
     }
 
+    /// Configures the timeout for establishing outgoing connections.
+    ///
+    /// Dials that do not complete within the timeout, e.g. because the remote drops the SYN
+    /// packets, fail with [`io::ErrorKind::TimedOut`] instead of waiting for the OS timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
     /// Configures port reuse for local sockets, which implies
     /// reuse of listening ports for outgoing connections to
     /// enhance NAT traversal capabilities.
@@ -487,6 +501,8 @@ where
             .set_nonblocking(true)
             .map_err(TransportError::Other)?;
 
+        let connect_timeout = self.config.connect_timeout;
+
         Ok(async move {
             // [`Transport::dial`] should do no work unless the returned [`Future`] is polled. Thus
             // do the `connect` call within the [`Future`].
@@ -497,8 +513,13 @@ where
                 Err(err) => return Err(err),
             };
 
-            let stream = T::new_stream(socket.into()).await?;
-            Ok(stream)
+            match future::select(T::new_stream(socket.into()), Delay::new(connect_timeout)).await {
+                future::Either::Left((stream, _)) => stream,
+                future::Either::Right(((), _)) => Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("connecting to {socket_addr} timed out"),
+                )),
+            }
         }
         .boxed())
     }
@@ -566,7 +587,10 @@ where
     /// when listening on all interfaces for IPv4 respectively IPv6 connections.
     listen_addr: SocketAddr,
     /// The async listening socket for incoming connections.
-    listener: T::Listener,
+    ///
+    /// `None` once the listener is closed, to release the socket before
+    /// [`TransportEvent::ListenerClosed`] is reported.
+    listener: Option<T::Listener>,
     /// Watcher for network interface changes.
     /// Reports [`IfEvent`]s for new / deleted ip-addresses when interfaces
     /// become or stop being available.
@@ -610,7 +634,7 @@ where
 
         Ok(ListenStream {
             port_reuse,
-            listener,
+            listener: Some(listener),
             listener_id,
             listen_addr,
             if_watcher,
@@ -655,6 +679,8 @@ where
             reason,
         });
         self.is_closed = true;
+        // Release the socket so that its address can be listened on again right away.
+        self.listener = None;
 
         // Wake the stream to deliver the last event.
         if let Some(waker) = self.close_listener_waker.take() {
@@ -752,8 +778,13 @@ where
             return Poll::Ready(Some(event));
         }
 
+        let listener = self
+            .listener
+            .as_mut()
+            .expect("listener to be present until closed");
+
         // Take the pending connection from the backlog.
-        match T::poll_accept(&mut self.listener, cx) {
+        match T::poll_accept(listener, cx) {
             Poll::Ready(Ok(Incoming {
                 local_addr,
                 remote_addr,
@@ -1329,6 +1360,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn listen_on_same_port_after_listener_closed() {
+        env_logger::try_init().ok();
+
+        async fn relisten<T: Provider>() {
+            let mut tcp = Transport::<T>::default().boxed();
+            let listener_id = ListenerId::next();
+            tcp.listen_on(listener_id, "/ip4/127.0.0.1/tcp/0".parse().unwrap())
+                .unwrap();
+            let addr = tcp
+                .select_next_some()
+                .await
+                .into_new_address()
+                .expect("listen address");
+
+            assert!(tcp.remove_listener(listener_id));
+            loop {
+                if let TransportEvent::ListenerClosed {
+                    listener_id: id, ..
+                } = tcp.select_next_some().await
+                {
+                    assert_eq!(id, listener_id);
+                    break;
+                }
+            }
+
+            tcp.listen_on(ListenerId::next(), addr.clone()).unwrap();
+            let new_addr = tcp
+                .select_next_some()
+                .await
+                .into_new_address()
+                .expect("listen address");
+            assert_eq!(new_addr, addr);
+        }
+
+        #[cfg(feature = "async-io")]
+        {
+            async_std::task::block_on(relisten::<async_io::Tcp>());
+        }
+
+        #[cfg(feature = "tokio")]
+        {
+            let rt = ::tokio::runtime::Builder::new_current_thread()
+                .enable_io()
+                .build()
+                .unwrap();
+            rt.block_on(relisten::<tokio::Tcp>());
+        }
+    }
+
     #[test]
     fn test_listens_ipv4_ipv6_separately() {
         fn test<T: Provider>() {
@@ -1366,4 +1447,47 @@ mod tests {
             });
         }
     }
+
+    #[test]
+    fn connect_timeout() {
+        env_logger::try_init().ok();
+
+        async fn dial<T: Provider>() {
+            // A listener with a full accept queue silently drops further connection attempts.
+            let listener =
+                Socket::new(Domain::IPV4, Type::STREAM, Some(socket2::Protocol::TCP)).unwrap();
+            listener
+                .bind(&SocketAddr::from(([127, 0, 0, 1], 0)).into())
+                .unwrap();
+            listener.listen(0).unwrap();
+            let listen_addr = listener.local_addr().unwrap().as_socket().unwrap();
+            let _queued = std::net::TcpStream::connect(listen_addr).unwrap();
+
+            let timeout = Duration::from_millis(500);
+            let mut tcp = Transport::<T>::new(Config::new().connect_timeout(timeout));
+            let start = std::time::Instant::now();
+            let error = tcp
+                .dial(ip_to_multiaddr(listen_addr.ip(), listen_addr.port()))
+                .unwrap()
+                .await
+                .unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+            assert!(start.elapsed() < timeout * 2);
+        }
+
+        #[cfg(feature = "async-io")]
+        {
+            async_std::task::block_on(dial::<async_io::Tcp>());
+        }
+
+        #[cfg(feature = "tokio")]
+        {
+            let rt = ::tokio::runtime::Builder::new_current_thread()
+                .enable_io()
+                .build()
+                .unwrap();
+            rt.block_on(dial::<tokio::Tcp>());
+        }
+    }
 }
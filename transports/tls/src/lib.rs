@@ -46,6 +46,21 @@ pub fn make_client_config(
 ) -> Result<rustls::ClientConfig, certificate::GenError> {
     let (certificate, private_key) = certificate::generate(keypair)?;
 
+    let crypto = make_client_config_with_certificate(certificate, private_key, remote_peer_id)
+        .expect("Generated certificate and key are valid; qed");
+
+    Ok(crypto)
+}
+
+/// Create a TLS client configuration for libp2p, presenting the given certificate.
+///
+/// The certificate and private key must have been created with [`certificate::generate`].
+/// Fails if the private key is invalid or not supported.
+pub fn make_client_config_with_certificate(
+    certificate: rustls::Certificate,
+    private_key: rustls::PrivateKey,
+    remote_peer_id: Option<PeerId>,
+) -> Result<rustls::ClientConfig, rustls::Error> {
     let mut crypto = rustls::ClientConfig::builder()
         .with_cipher_suites(verifier::CIPHERSUITES)
         .with_safe_default_kx_groups()
@@ -54,8 +69,7 @@ pub fn make_client_config(
         .with_custom_certificate_verifier(Arc::new(
             verifier::Libp2pCertificateVerifier::with_remote_peer_id(remote_peer_id),
         ))
-        .with_client_auth_cert(vec![certificate], private_key)
-        .expect("Client cert key DER is valid; qed");
+        .with_client_auth_cert(vec![certificate], private_key)?;
     crypto.alpn_protocols = vec![P2P_ALPN.to_vec()];
 
     Ok(crypto)
@@ -67,14 +81,27 @@ pub fn make_server_config(
 ) -> Result<rustls::ServerConfig, certificate::GenError> {
     let (certificate, private_key) = certificate::generate(keypair)?;
 
+    let crypto = make_server_config_with_certificate(certificate, private_key)
+        .expect("Generated certificate and key are valid; qed");
+
+    Ok(crypto)
+}
+
+/// Create a TLS server configuration for libp2p, presenting the given certificate.
+///
+/// The certificate and private key must have been created with [`certificate::generate`].
+/// Fails if the private key is invalid or not supported.
+pub fn make_server_config_with_certificate(
+    certificate: rustls::Certificate,
+    private_key: rustls::PrivateKey,
+) -> Result<rustls::ServerConfig, rustls::Error> {
     let mut crypto = rustls::ServerConfig::builder()
         .with_cipher_suites(verifier::CIPHERSUITES)
         .with_safe_default_kx_groups()
         .with_protocol_versions(verifier::PROTOCOL_VERSIONS)
         .expect("Cipher suites and kx groups are configured; qed")
         .with_client_cert_verifier(Arc::new(verifier::Libp2pCertificateVerifier::new()))
-        .with_single_cert(vec![certificate], private_key)
-        .expect("Server cert key DER is valid; qed");
+        .with_single_cert(vec![certificate], private_key)?;
     crypto.alpn_protocols = vec![P2P_ALPN.to_vec()];
 
     Ok(crypto)
@@ -36,9 +36,9 @@ pub enum UpgradeError {
     #[error("Failed to generate certificate")]
     CertificateGeneration(#[from] certificate::GenError),
     #[error("Failed to upgrade server connection")]
-    ServerUpgrade(std::io::Error),
+    ServerUpgrade(#[source] std::io::Error),
     #[error("Failed to upgrade client connection")]
-    ClientUpgrade(std::io::Error),
+    ClientUpgrade(#[source] std::io::Error),
     #[error("Failed to parse certificate")]
     BadCertificate(#[from] certificate::ParseError),
 }
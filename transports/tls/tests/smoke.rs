@@ -56,6 +56,21 @@ async fn can_establish_connection() {
     assert_eq!(&outbound_peer_id, swarm1.local_peer_id());
 }
 
+#[test]
+fn config_with_invalid_private_key_is_rejected() {
+    let identity = libp2p_identity::Keypair::generate_ed25519();
+    let (certificate, _) = libp2p_tls::certificate::generate(&identity).unwrap();
+    let invalid_key = rustls::PrivateKey(vec![0; 32]);
+
+    assert!(libp2p_tls::make_client_config_with_certificate(
+        certificate.clone(),
+        invalid_key.clone(),
+        None
+    )
+    .is_err());
+    assert!(libp2p_tls::make_server_config_with_certificate(certificate, invalid_key).is_err());
+}
+
 fn make_swarm() -> Swarm<dummy::Behaviour> {
     let identity = libp2p_identity::Keypair::generate_ed25519();
 
@@ -19,6 +19,7 @@
 // DEALINGS IN THE SOFTWARE.
 
 use futures::{future::BoxFuture, prelude::*, stream::SelectAll, stream::Stream};
+use futures_timer::Delay;
 use if_watch::{tokio::IfWatcher, IfEvent};
 use libp2p_core::{
     multiaddr::{Multiaddr, Protocol},
@@ -30,10 +31,12 @@ use webrtc::peer_connection::configuration::RTCConfiguration;
 
 use std::net::IpAddr;
 use std::{
+    collections::{HashSet, VecDeque},
     io,
     net::SocketAddr,
     pin::Pin,
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 
 use crate::tokio::{
@@ -51,6 +54,8 @@ pub struct Transport {
     config: Config,
     /// All the active listeners.
     listeners: SelectAll<ListenStream>,
+    /// Interval and timer for the automatic rotation of the certificate, if enabled.
+    certificate_rotation: Option<(Duration, Delay)>,
 }
 
 impl Transport {
@@ -70,8 +75,49 @@ impl Transport {
         Self {
             config: Config::new(id_keys, certificate),
             listeners: SelectAll::new(),
+            certificate_rotation: None,
         }
     }
+
+    /// Returns the fingerprint of the certificate currently used for new connections.
+    ///
+    /// This is the fingerprint contained in our listen addresses. It can be handed to remotes
+    /// out of band, e.g. by a custom signaling protocol.
+    pub fn local_fingerprint(&self) -> Fingerprint {
+        self.config.fingerprint
+    }
+
+    /// Replaces the certificate used for new connections.
+    ///
+    /// Existing connections keep using the certificate they were established with.
+    /// Because the certificate's fingerprint is part of our listen addresses, every listener
+    /// reports its current addresses as expired and the addresses with the new fingerprint as new.
+    ///
+    /// Returns the fingerprint of the replaced certificate.
+    pub fn rotate_certificate(&mut self, certificate: Certificate) -> Fingerprint {
+        let old_fingerprint = self.config.fingerprint;
+        self.config = Config::new(self.config.id_keys.clone(), certificate);
+
+        log::debug!(
+            "Rotated certificate from {} to {}",
+            old_fingerprint.to_sdp_format(),
+            self.config.fingerprint.to_sdp_format()
+        );
+
+        for listener in self.listeners.iter_mut() {
+            listener.set_config(self.config.clone());
+        }
+
+        old_fingerprint
+    }
+
+    /// Automatically replaces the certificate with a newly generated one every `interval`.
+    ///
+    /// See [`Transport::rotate_certificate`].
+    pub fn with_certificate_rotation(mut self, interval: Duration) -> Self {
+        self.certificate_rotation = Some((interval, Delay::new(interval)));
+        self
+    }
 }
 
 impl libp2p_core::Transport for Transport {
@@ -112,6 +158,18 @@ impl libp2p_core::Transport for Transport {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+        if let Some((interval, delay)) = self.certificate_rotation.as_mut() {
+            if delay.poll_unpin(cx).is_ready() {
+                delay.reset(*interval);
+                match Certificate::generate(&mut rand::thread_rng()) {
+                    Ok(certificate) => {
+                        self.rotate_certificate(certificate);
+                    }
+                    Err(e) => log::warn!("Failed to generate new certificate: {e}"),
+                }
+            }
+        }
+
         match self.listeners.poll_next_unpin(cx) {
             Poll::Ready(Some(ev)) => Poll::Ready(ev),
             _ => Poll::Pending,
@@ -196,8 +254,11 @@ struct ListenStream {
     /// `None` if the socket is only listening on a single interface.
     if_watcher: Option<IfWatcher>,
 
-    /// Pending event to reported.
-    pending_event: Option<<Self as Stream>::Item>,
+    /// The IP addresses we have reported a [`TransportEvent::NewAddress`] for.
+    reported_ips: HashSet<IpAddr>,
+
+    /// Pending events to be reported.
+    pending_events: VecDeque<<Self as Stream>::Item>,
 
     /// The stream must be awaken after it has been closed to deliver the last event.
     close_listener_waker: Option<Waker>,
@@ -209,14 +270,15 @@ impl ListenStream {
         let listen_addr = udp_mux.listen_addr();
 
         let if_watcher;
-        let pending_event;
+        let mut reported_ips = HashSet::new();
+        let mut pending_events = VecDeque::new();
         if listen_addr.ip().is_unspecified() {
             if_watcher = Some(IfWatcher::new()?);
-            pending_event = None;
         } else {
             if_watcher = None;
             let ma = socketaddr_to_multiaddr(&listen_addr, Some(config.fingerprint));
-            pending_event = Some(TransportEvent::NewAddress {
+            reported_ips.insert(listen_addr.ip());
+            pending_events.push_back(TransportEvent::NewAddress {
                 listener_id,
                 listen_addr: ma,
             })
@@ -229,11 +291,37 @@ impl ListenStream {
             udp_mux,
             report_closed: None,
             if_watcher,
-            pending_event,
+            reported_ips,
+            pending_events,
             close_listener_waker: None,
         })
     }
 
+    /// Replaces the config used for new connections and re-reports our listen addresses,
+    /// which contain the certificate's fingerprint.
+    fn set_config(&mut self, config: Config) {
+        for ip in self.reported_ips.iter() {
+            self.pending_events
+                .push_back(TransportEvent::AddressExpired {
+                    listener_id: self.listener_id,
+                    listen_addr: self.listen_multiaddress(*ip),
+                });
+        }
+
+        self.config = config;
+
+        for ip in self.reported_ips.iter() {
+            self.pending_events.push_back(TransportEvent::NewAddress {
+                listener_id: self.listener_id,
+                listen_addr: self.listen_multiaddress(*ip),
+            });
+        }
+
+        if let Some(waker) = self.close_listener_waker.take() {
+            waker.wake();
+        }
+    }
+
     /// Report the listener as closed in a [`TransportEvent::ListenerClosed`] and
     /// terminate the stream.
     fn close(&mut self, reason: Result<(), Error>) {
@@ -269,6 +357,7 @@ impl ListenStream {
                     if self.listen_addr.is_ipv4() == ip.is_ipv4()
                         || self.listen_addr.is_ipv6() == ip.is_ipv6()
                     {
+                        self.reported_ips.insert(ip);
                         return Poll::Ready(TransportEvent::NewAddress {
                             listener_id: self.listener_id,
                             listen_addr: self.listen_multiaddress(ip),
@@ -280,6 +369,7 @@ impl ListenStream {
                     if self.listen_addr.is_ipv4() == ip.is_ipv4()
                         || self.listen_addr.is_ipv6() == ip.is_ipv6()
                     {
+                        self.reported_ips.remove(&ip);
                         return Poll::Ready(TransportEvent::AddressExpired {
                             listener_id: self.listener_id,
                             listen_addr: self.listen_multiaddress(ip),
@@ -311,7 +401,7 @@ impl Stream for ListenStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         loop {
-            if let Some(event) = self.pending_event.take() {
+            if let Some(event) = self.pending_events.pop_front() {
                 return Poll::Ready(Some(event));
             }
 
@@ -553,4 +643,47 @@ mod tests {
             assert!(transport.listeners.is_empty());
         }
     }
+
+    #[tokio::test]
+    async fn rotate_certificate_reports_new_addresses() {
+        let id_keys = identity::Keypair::generate_ed25519();
+        let old_certificate = Certificate::generate(&mut thread_rng()).unwrap();
+        let new_certificate = Certificate::generate(&mut thread_rng()).unwrap();
+        let mut transport = Transport::new(id_keys, old_certificate.clone());
+
+        let listener = ListenerId::next();
+        transport
+            .listen_on(
+                listener,
+                "/ip4/127.0.0.1/udp/0/webrtc-direct".parse().unwrap(),
+            )
+            .unwrap();
+        let old_addr = match poll_fn(|cx| Pin::new(&mut transport).as_mut().poll(cx)).await {
+            TransportEvent::NewAddress { listen_addr, .. } => listen_addr,
+            e => panic!("Unexpected event: {e:?}"),
+        };
+
+        assert_eq!(transport.local_fingerprint(), old_certificate.fingerprint());
+        let replaced = transport.rotate_certificate(new_certificate.clone());
+        assert_eq!(replaced, old_certificate.fingerprint());
+        assert_eq!(transport.local_fingerprint(), new_certificate.fingerprint());
+
+        match poll_fn(|cx| Pin::new(&mut transport).as_mut().poll(cx)).await {
+            TransportEvent::AddressExpired { listen_addr, .. } => {
+                assert_eq!(listen_addr, old_addr);
+            }
+            e => panic!("Unexpected event: {e:?}"),
+        }
+        match poll_fn(|cx| Pin::new(&mut transport).as_mut().poll(cx)).await {
+            TransportEvent::NewAddress { listen_addr, .. } => {
+                assert_eq!(
+                    listen_addr.iter().last(),
+                    Some(Protocol::Certhash(
+                        new_certificate.fingerprint().to_multihash()
+                    ))
+                );
+            }
+            e => panic!("Unexpected event: {e:?}"),
+        }
+    }
 }
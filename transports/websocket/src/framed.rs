@@ -19,6 +19,7 @@
 // DEALINGS IN THE SOFTWARE.
 
 use crate::{error::Error, tls};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
 use either::Either;
 use futures::{future::BoxFuture, prelude::*, ready, stream::BoxStream};
 use futures_rustls::{client, rustls, server};
@@ -41,6 +42,9 @@ use url::Url;
 /// Max. number of payload bytes of a single frame.
 const MAX_DATA_SIZE: usize = 256 * 1024 * 1024;
 
+/// Max. number of bytes of the response header of a proxy to a `CONNECT` request.
+const MAX_PROXY_RESPONSE_SIZE: usize = 8 * 1024;
+
 /// A Websocket transport whose output type is a [`Stream`] and [`Sink`] of
 /// frame payloads which does not implement [`AsyncRead`] or
 /// [`AsyncWrite`]. See [`crate::WsConfig`] if you require the latter.
@@ -55,6 +59,74 @@ pub struct WsConfig<T> {
     /// This is the suffix of the address provided in `listen_on`.
     /// Can only be [`Protocol::Ws`] or [`Protocol::Wss`].
     listener_protos: HashMap<ListenerId, Protocol<'static>>,
+    /// Headers to add to outbound upgrade requests.
+    additional_headers: Vec<(String, String)>,
+    /// Decides whether to accept inbound upgrade requests.
+    header_validator: Option<HeaderValidator>,
+    /// HTTP proxy to tunnel outbound connections through.
+    proxy: Option<ProxyConfig>,
+}
+
+/// An HTTP proxy that outbound connections are tunneled through, as set via
+/// [`WsConfig::set_proxy`].
+#[derive(Debug, Clone)]
+pub struct ProxyConfig {
+    addr: Multiaddr,
+    /// Base64 encoded `user:password` for basic authentication.
+    basic_auth: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Tunnel outbound connections through the HTTP proxy at the given address.
+    ///
+    /// The address is dialed with the inner transport, e.g. `/ip4/10.0.0.1/tcp/3128`.
+    pub fn new(addr: Multiaddr) -> Self {
+        ProxyConfig {
+            addr,
+            basic_auth: None,
+        }
+    }
+
+    /// Authenticate with the proxy using HTTP basic authentication.
+    pub fn with_basic_auth(mut self, user: &str, password: &str) -> Self {
+        self.basic_auth = Some(BASE64_STANDARD.encode(format!("{user}:{password}")));
+        self
+    }
+}
+
+/// An inbound websocket upgrade request, as passed to the function set via
+/// [`WsConfig::set_header_validator`].
+#[derive(Debug)]
+pub struct UpgradeRequest<'a> {
+    host: &'a [u8],
+    origin: Option<&'a [u8]>,
+    path: &'a str,
+}
+
+impl UpgradeRequest<'_> {
+    /// The value of the `Host` header.
+    pub fn host(&self) -> &[u8] {
+        self.host
+    }
+
+    /// The value of the `Origin` header, if any.
+    pub fn origin(&self) -> Option<&[u8]> {
+        self.origin
+    }
+
+    /// The requested resource path.
+    pub fn path(&self) -> &str {
+        self.path
+    }
+}
+
+#[derive(Clone)]
+struct HeaderValidator(Arc<dyn Fn(&UpgradeRequest<'_>) -> bool + Send + Sync>);
+
+impl fmt::Debug for HeaderValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HeaderValidator").finish()
+    }
 }
 
 impl<T> WsConfig<T>
@@ -69,6 +141,9 @@ where
             tls_config: tls::Config::client(),
             max_redirects: 0,
             listener_protos: HashMap::new(),
+            additional_headers: Vec::new(),
+            header_validator: None,
+            proxy: None,
         }
     }
 
@@ -99,6 +174,35 @@ where
         self.tls_config = c;
         self
     }
+
+    /// Set additional headers to send with the HTTP upgrade request when dialing,
+    /// e.g. an `Authorization` header.
+    pub fn set_additional_headers(&mut self, headers: Vec<(String, String)>) -> &mut Self {
+        self.additional_headers = headers;
+        self
+    }
+
+    /// Set a function that decides whether to accept an inbound HTTP upgrade request.
+    ///
+    /// Requests for which it returns `false` are rejected with status code 403
+    /// before any libp2p protocol is negotiated.
+    pub fn set_header_validator(
+        &mut self,
+        validator: impl Fn(&UpgradeRequest<'_>) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.header_validator = Some(HeaderValidator(Arc::new(validator)));
+        self
+    }
+
+    /// Tunnel outbound connections through the given HTTP proxy.
+    ///
+    /// Instead of the remote's address, the proxy is dialed and asked to open a tunnel to the
+    /// remote with an HTTP/1.1 `CONNECT` request. The TLS and websocket handshakes then happen
+    /// through that tunnel.
+    pub fn set_proxy(&mut self, proxy: ProxyConfig) -> &mut Self {
+        self.proxy = Some(proxy);
+        self
+    }
 }
 
 type TlsOrPlain<T> = future::Either<future::Either<client::TlsStream<T>, server::TlsStream<T>>, T>;
@@ -280,11 +384,20 @@ where
         let transport = self.transport.clone();
         let tls_config = self.tls_config.clone();
         let max_redirects = self.max_redirects;
+        let additional_headers = self.additional_headers.clone();
+        let proxy = self.proxy.clone();
 
         let future = async move {
             loop {
-                match Self::dial_once(transport.clone(), addr, tls_config.clone(), role_override)
-                    .await
+                match Self::dial_once(
+                    transport.clone(),
+                    addr,
+                    tls_config.clone(),
+                    &additional_headers,
+                    proxy.as_ref(),
+                    role_override,
+                )
+                .await
                 {
                     Ok(Either::Left(redirect)) => {
                         if remaining_redirects == 0 {
@@ -308,20 +421,37 @@ where
         transport: Arc<Mutex<T>>,
         addr: WsAddress,
         tls_config: tls::Config,
+        additional_headers: &[(String, String)],
+        proxy: Option<&ProxyConfig>,
         role_override: Endpoint,
     ) -> Result<Either<String, Connection<T::Output>>, Error<T::Error>> {
         trace!("Dialing websocket address: {:?}", addr);
 
+        let tcp_addr = match proxy {
+            Some(proxy) => proxy.addr.clone(),
+            None => addr.tcp_addr,
+        };
         let dial = match role_override {
-            Endpoint::Dialer => transport.lock().dial(addr.tcp_addr),
-            Endpoint::Listener => transport.lock().dial_as_listener(addr.tcp_addr),
+            Endpoint::Dialer => transport.lock().dial(tcp_addr),
+            Endpoint::Listener => transport.lock().dial_as_listener(tcp_addr),
         }
         .map_err(|e| match e {
             TransportError::MultiaddrNotSupported(a) => Error::InvalidMultiaddr(a),
             TransportError::Other(e) => Error::Transport(e),
         })?;
 
-        let stream = dial.map_err(Error::Transport).await?;
+        let mut stream = dial.map_err(Error::Transport).await?;
+
+        if let Some(proxy) = proxy {
+            trace!(
+                "Opening tunnel to {} through {}",
+                addr.host_port,
+                proxy.addr
+            );
+            connect_through_proxy(&mut stream, &addr.host_port, proxy)
+                .map_err(|e| Error::Handshake(Box::new(e)))
+                .await?;
+        }
         trace!("TCP connection to {} established.", addr.host_port);
 
         let stream = if addr.use_tls {
@@ -348,7 +478,15 @@ where
 
         trace!("Sending websocket handshake to {}", addr.host_port);
 
+        let headers = additional_headers
+            .iter()
+            .map(|(name, value)| handshake::client::Header {
+                name,
+                value: value.as_bytes(),
+            })
+            .collect::<Vec<_>>();
         let mut client = handshake::Client::new(stream, &addr.host_port, addr.path.as_ref());
+        client.set_headers(&headers);
 
         match client
             .handshake()
@@ -385,6 +523,7 @@ where
         let remote_addr2 = remote_addr.clone(); // used for logging
         let tls_config = self.tls_config.clone();
         let max_size = self.max_data_size;
+        let header_validator = self.header_validator.clone();
 
         async move {
             let stream = upgrade.map_err(Error::Transport).await?;
@@ -421,14 +560,36 @@ where
 
             let mut server = handshake::Server::new(stream);
 
-            let ws_key = {
+            let (ws_key, accepted) = {
                 let request = server
                     .receive_request()
                     .map_err(|e| Error::Handshake(Box::new(e)))
                     .await?;
-                request.key()
+                let accepted = header_validator.map_or(true, |HeaderValidator(validator)| {
+                    let headers = request.headers();
+                    validator(&UpgradeRequest {
+                        host: headers.host,
+                        origin: headers.origin,
+                        path: request.path(),
+                    })
+                });
+                (request.key(), accepted)
             };
 
+            if !accepted {
+                debug!(
+                    "rejecting websocket handshake request from {}",
+                    remote_addr2
+                );
+
+                server
+                    .send_response(&handshake::server::Response::Reject { status_code: 403 })
+                    .map_err(|e| Error::Handshake(Box::new(e)))
+                    .await?;
+
+                return Err(Error::Handshake("upgrade request rejected".into()));
+            }
+
             trace!(
                 "accepting websocket handshake request from {}",
                 remote_addr2
@@ -466,6 +627,56 @@ struct WsAddress {
     tcp_addr: Multiaddr,
 }
 
+/// Asks the proxy to open a tunnel to `host_port` with an HTTP/1.1 `CONNECT` request.
+async fn connect_through_proxy<S>(
+    stream: &mut S,
+    host_port: &str,
+    proxy: &ProxyConfig,
+) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut request = format!("CONNECT {host_port} HTTP/1.1\r\nHost: {host_port}\r\n");
+    if let Some(basic_auth) = &proxy.basic_auth {
+        request.push_str(&format!("Proxy-Authorization: Basic {basic_auth}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    // Read the response header byte by byte, the tunneled data following it must not be consumed.
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() == MAX_PROXY_RESPONSE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "proxy response header too large",
+            ));
+        }
+        let mut byte = [0];
+        stream.read_exact(&mut byte).await?;
+        response.push(byte[0]);
+    }
+
+    // Any 2xx status code means the tunnel has been established.
+    let status_code = response
+        .split(|b| *b == b' ')
+        .nth(1)
+        .and_then(|code| std::str::from_utf8(code).ok())
+        .and_then(|code| code.parse::<u16>().ok());
+    match status_code {
+        Some(200..=299) => Ok(()),
+        Some(code) => Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!("proxy refused to open a tunnel; status code = {code}"),
+        )),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid proxy response",
+        )),
+    }
+}
+
 /// Tries to parse the given `Multiaddr` into a `WsAddress` used
 /// for dialing.
 ///
@@ -484,7 +695,7 @@ fn parse_ws_dial_addr<T>(addr: Multiaddr) -> Result<WsAddress, Error<T>> {
                 break (format!("{ip}:{port}"), None)
             }
             (Some(Protocol::Ip6(ip)), Some(Protocol::Tcp(port))) => {
-                break (format!("{ip}:{port}"), None)
+                break (format!("[{ip}]:{port}"), None)
             }
             (Some(Protocol::Dns(h)), Some(Protocol::Tcp(port)))
             | (Some(Protocol::Dns4(h)), Some(Protocol::Tcp(port)))